@@ -0,0 +1,162 @@
+use std::{
+    alloc::{GlobalAlloc, Layout, System},
+    cell::Cell,
+    fmt::Display,
+};
+
+#[cfg(feature = "alloc-stats")]
+#[global_allocator]
+static GLOBAL: Counting = Counting;
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct AllocStats {
+    pub allocations: u64,
+    pub bytes: u64,
+    pub peak: u64,
+}
+
+impl Display for AllocStats {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{} allocations, {} allocated, {} peak",
+            self.allocations,
+            format_bytes(self.bytes),
+            format_bytes(self.peak)
+        )
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+struct Counters {
+    allocations: u64,
+    bytes: u64,
+    live: i64,
+    peak: i64,
+}
+
+thread_local! {
+    static COUNTERS: Cell<Counters> = const {
+        Cell::new(Counters {
+            allocations: 0,
+            bytes: 0,
+            live: 0,
+            peak: 0,
+        })
+    };
+}
+
+pub struct Counting;
+
+unsafe impl GlobalAlloc for Counting {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        record(layout.size(), 0);
+        System.alloc(layout)
+    }
+
+    unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+        record(layout.size(), 0);
+        System.alloc_zeroed(layout)
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        record(new_size, layout.size());
+        System.realloc(ptr, layout, new_size)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        let _ = COUNTERS.try_with(|counters| {
+            let mut current = counters.get();
+            current.live -= layout.size() as i64;
+            counters.set(current);
+        });
+        System.dealloc(ptr, layout)
+    }
+}
+
+fn record(size: usize, freed: usize) {
+    let _ = COUNTERS.try_with(|counters| {
+        let mut current = counters.get();
+        current.allocations += 1;
+        current.bytes += size as u64;
+        current.live += size as i64 - freed as i64;
+        current.peak = current.peak.max(current.live);
+        counters.set(current);
+    });
+}
+
+pub fn enabled() -> bool {
+    cfg!(feature = "alloc-stats")
+}
+
+pub fn measure<T>(f: impl FnOnce() -> T) -> (T, Option<AllocStats>) {
+    if !enabled() {
+        return (f(), None);
+    }
+
+    let before = COUNTERS.with(|counters| {
+        let mut current = counters.get();
+        current.peak = current.live;
+        counters.set(current);
+        current
+    });
+    let result = f();
+    let after = COUNTERS.with(Cell::get);
+
+    let stats = AllocStats {
+        allocations: after.allocations - before.allocations,
+        bytes: after.bytes - before.bytes,
+        peak: (after.peak - before.live).max(0) as u64,
+    };
+    (result, Some(stats))
+}
+
+pub fn format_bytes(bytes: u64) -> String {
+    const UNITS: [&str; 4] = ["KiB", "MiB", "GiB", "TiB"];
+
+    if bytes < 1024 {
+        return format!("{bytes} B");
+    }
+    let mut value = bytes as f64 / 1024.0;
+    let mut unit = 0;
+    while value >= 1024.0 && unit < UNITS.len() - 1 {
+        value /= 1024.0;
+        unit += 1;
+    }
+    format!("{value:.2} {}", UNITS[unit])
+}
+
+#[cfg(test)]
+mod tests {
+    use std::hint::black_box;
+
+    use super::{enabled, format_bytes, measure};
+
+    #[test]
+    fn allocations_are_counted_when_enabled() {
+        let (len, stats) = measure(|| {
+            let first = black_box(vec![0u8; 4096]);
+            drop(first);
+            let second = black_box(vec![0u8; 1024]);
+            second.len()
+        });
+        assert_eq!(len, 1024);
+
+        match stats {
+            Some(stats) => {
+                assert!(enabled());
+                assert!(stats.allocations >= 2);
+                assert!(stats.bytes >= 5120);
+                assert!((4096..5120).contains(&stats.peak));
+            }
+            None => assert!(!enabled()),
+        }
+    }
+
+    #[test]
+    fn bytes_are_formatted() {
+        assert_eq!(format_bytes(512), "512 B");
+        assert_eq!(format_bytes(1536), "1.50 KiB");
+        assert_eq!(format_bytes(3 * 1024 * 1024), "3.00 MiB");
+    }
+}
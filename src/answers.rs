@@ -0,0 +1,89 @@
+use std::{collections::BTreeMap, fs, path::Path};
+
+use crate::{
+    cli::{Part, FIRST_DAY, LAST_DAY},
+    error::{AocError, Result},
+    toml::Document,
+};
+
+pub const DEFAULT_ANSWERS_FILE: &str = "answers.toml";
+
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct ExpectedAnswers {
+    answers: BTreeMap<(u8, Part), String>,
+}
+
+impl ExpectedAnswers {
+    pub fn parse(input: &str) -> Result<Self> {
+        let document = Document::parse(input)?;
+        let mut expected = ExpectedAnswers::default();
+
+        if let Some(key) = document.root().and_then(|root| root.keys().next()) {
+            return Err(AocError::parse(format!(
+                "`{key}` must be inside a `[dayNN]` table"
+            )));
+        }
+        for (name, table) in document.tables() {
+            let day = name
+                .strip_prefix("day")
+                .and_then(|day| day.parse::<u8>().ok())
+                .filter(|day| (FIRST_DAY..=LAST_DAY).contains(day))
+                .ok_or_else(|| AocError::parse(format!("unknown table `[{name}]`")))?;
+
+            for (key, value) in table {
+                let part = match key.as_str() {
+                    "part1" => Part::One,
+                    "part2" => Part::Two,
+                    _ => {
+                        return Err(AocError::parse(format!(
+                            "unknown key `{key}` in `[{name}]`, expected `part1` or `part2`"
+                        )))
+                    }
+                };
+                expected.answers.insert((day, part), value.to_string());
+            }
+        }
+        Ok(expected)
+    }
+
+    pub fn read(path: &Path) -> Result<Self> {
+        Self::parse(&fs::read_to_string(path)?)
+    }
+
+    pub fn get(&self, day: u8, part: Part) -> Option<&str> {
+        self.answers.get(&(day, part)).map(String::as_str)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::cli::Part;
+
+    use super::ExpectedAnswers;
+
+    #[test]
+    fn answers_are_parsed() {
+        let expected = ExpectedAnswers::parse(
+            "[day01]
+part1 = 55607
+part2 = 55291
+
+[day09]
+part2 = -3
+",
+        )
+        .unwrap();
+
+        assert_eq!(expected.get(1, Part::One), Some("55607"));
+        assert_eq!(expected.get(9, Part::Two), Some("-3"));
+        assert_eq!(expected.get(9, Part::One), None);
+    }
+
+    #[test]
+    fn unknown_tables_and_keys_are_errors() {
+        assert!(ExpectedAnswers::parse("part1 = 1").is_err());
+        assert!(ExpectedAnswers::parse("[day26]\npart1 = 1").is_err());
+        assert!(ExpectedAnswers::parse("[answers]\npart1 = 1").is_err());
+        assert!(ExpectedAnswers::parse("[day01]\npart3 = 1").is_err());
+    }
+}
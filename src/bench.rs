@@ -0,0 +1,145 @@
+use std::{
+    fmt::Display,
+    hint::black_box,
+    time::{Duration, Instant},
+};
+
+use crate::{cli::Part, error::Result, params::Params, solution::Solution};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Stats {
+    pub min: Duration,
+    pub median: Duration,
+    pub mean: Duration,
+    pub stddev: Duration,
+}
+
+impl Stats {
+    pub fn from_samples(samples: &[Duration]) -> Option<Self> {
+        let mut sorted = samples.to_vec();
+        sorted.sort_unstable();
+
+        let len = sorted.len();
+        let min = *sorted.first()?;
+        let median = if len.is_multiple_of(2) {
+            (sorted[len / 2 - 1] + sorted[len / 2]) / 2
+        } else {
+            sorted[len / 2]
+        };
+        let mean_nanos = sorted.iter().map(Duration::as_nanos).sum::<u128>() as f64 / len as f64;
+        let variance = sorted
+            .iter()
+            .map(|sample| (sample.as_nanos() as f64 - mean_nanos).powi(2))
+            .sum::<f64>()
+            / len as f64;
+
+        Some(Stats {
+            min,
+            median,
+            mean: Duration::from_nanos(mean_nanos.round() as u64),
+            stddev: Duration::from_nanos(variance.sqrt().round() as u64),
+        })
+    }
+}
+
+impl Display for Stats {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "min {:>9}  median {:>9}  mean {:>9}  stddev {:>9}",
+            format_duration(self.min),
+            format_duration(self.median),
+            format_duration(self.mean),
+            format_duration(self.stddev)
+        )
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PartBench {
+    pub parse: Stats,
+    pub solve: Stats,
+}
+
+pub fn bench_part(
+    solution: &dyn Solution,
+    part: Part,
+    input: &str,
+    params: &Params,
+    iterations: u32,
+) -> Result<Option<PartBench>> {
+    let mut parse_samples = Vec::with_capacity(iterations as usize);
+    let mut solve_samples = Vec::with_capacity(iterations as usize);
+
+    for _ in 0..iterations {
+        let start = Instant::now();
+        let solver = match part {
+            Part::One => solution.parse_part_one(black_box(input), params)?,
+            Part::Two => match solution.parse_part_two(black_box(input), params)? {
+                Some(solver) => solver,
+                None => return Ok(None),
+            },
+        };
+        let parsed = Instant::now();
+        black_box(solver()?);
+        let solved = Instant::now();
+
+        parse_samples.push(parsed - start);
+        solve_samples.push(solved - parsed);
+    }
+
+    match (
+        Stats::from_samples(&parse_samples),
+        Stats::from_samples(&solve_samples),
+    ) {
+        (Some(parse), Some(solve)) => Ok(Some(PartBench { parse, solve })),
+        _ => Ok(None),
+    }
+}
+
+pub fn format_duration(duration: Duration) -> String {
+    let nanos = duration.as_nanos();
+
+    match nanos {
+        0..=999 => format!("{nanos}ns"),
+        1_000..=999_999 => format!("{:.2}µs", nanos as f64 / 1e3),
+        1_000_000..=999_999_999 => format!("{:.2}ms", nanos as f64 / 1e6),
+        _ => format!("{:.2}s", nanos as f64 / 1e9),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use crate::{cli::Part, day06::Day06, params::Params};
+
+    use super::{bench_part, format_duration, Stats};
+
+    #[test]
+    fn stats_are_computed_from_samples() {
+        let samples: Vec<Duration> = [4, 2, 8, 6].into_iter().map(Duration::from_nanos).collect();
+        let stats = Stats::from_samples(&samples).unwrap();
+
+        assert_eq!(stats.min, Duration::from_nanos(2));
+        assert_eq!(stats.median, Duration::from_nanos(5));
+        assert_eq!(stats.mean, Duration::from_nanos(5));
+        assert_eq!(stats.stddev, Duration::from_nanos(2));
+        assert!(Stats::from_samples(&[]).is_none());
+        assert_eq!(format_duration(Duration::from_nanos(999)), "999ns");
+        assert_eq!(format_duration(Duration::from_micros(1500)), "1.50ms");
+    }
+
+    #[test]
+    fn parts_are_benchmarked() {
+        let input = "Time:      7  15   30\nDistance:  9  40  200";
+
+        assert!(bench_part(&Day06, Part::One, input, &Params::default(), 3)
+            .unwrap()
+            .is_some());
+        assert!(bench_part(&Day06, Part::Two, input, &Params::default(), 0)
+            .unwrap()
+            .is_none());
+        assert!(bench_part(&Day06, Part::One, "Time: 7", &Params::default(), 3).is_err());
+    }
+}
@@ -0,0 +1,1210 @@
+use std::{ops::RangeInclusive, path::PathBuf, time::Duration};
+
+use crate::{
+    config::Config,
+    differential::{self, DEFAULT_CASES},
+    fixtures::DEFAULT_FIXTURES_DIR,
+    generate::{DEFAULT_SEED, DEFAULT_SIZE},
+    input::InputSource,
+    report::Format,
+    robustness::DEFAULT_MUTATIONS,
+    scaffold::DEFAULT_SOURCE_DIR,
+    submit::DEFAULT_LOG_FILE,
+    trace::{Filter, Level},
+    watch::DEFAULT_INTERVAL,
+};
+
+pub const FIRST_DAY: u8 = 1;
+pub const LAST_DAY: u8 = 25;
+pub const DEFAULT_ITERATIONS: u32 = 10;
+
+pub const USAGE: &str = "Usage: aoc2023 <command> [options]
+
+Commands:
+    run <days> [--part <1|2>]    Run the selected days, e.g. `7`, `3..=9`, `1,4,6` or `all`
+    bench <days> [-n <count>]    Time parsing and solving of the selected days separately
+    verify <days>                Check the answers of the selected days against `answers.toml`
+    new <day> [--title <title>]  Generate and register `src/dayNN/mod.rs` for a new day
+    fetch <days>                 Download missing inputs using $AOC_SESSION or `session` in the config
+    submit <day> <1|2>           Post the computed answer unless the submission log rules it out
+    examples <day>               Extract examples and their answers from a saved puzzle page
+    watch <day>                  Re-run a day whenever its input or example files change
+    generate <day>               Print a random but valid input for the day
+    fuzz <days>                  Check that corrupted inputs fail with an error instead of a panic
+    compare [<pairs>]            Cross-check reference and optimized implementations on random inputs
+    shrink <day>                 Minimize an input that panics or gives a wrong answer into a fixture
+                                 (wrong answers go to fixtures/shrunk/ until their answer is known)
+    help                         Print this message
+
+Options:
+    -p, --part <1|2>             Only run the given part
+    -i, --input <path>           Read the puzzle input from a file, or from stdin with `-`
+    -q, --quiet                  Only print the answers, one per line
+    -f, --format <text|json|csv> Print one record per day and part in the given format
+    -j, --jobs <count>           Run day/part pairs on this many threads (default 1), and compare
+                                 the wall time with the per-part wall times added up; std has
+                                 no CPU clock, so time spent waiting on other threads counts too
+    -v, --verbose                Trace intermediate values to stderr, repeat for more detail (-vvv)
+    --trace <days>               Only trace the given days, e.g. `--trace day07` (debug builds only)
+    --param <key>=<value>        Override a puzzle parameter, e.g. `--param part2_expansion=10`
+    -n, --iterations <count>     Number of timed runs per part when benchmarking (default 10), or
+                                 of corrupted inputs per day when fuzzing (default 1000), or of
+                                 generated inputs per pair when comparing (default 200)
+    --input-dir <dir>            Read `<dir>/dayNN/input.txt` (defaults to the crate's src/)
+    --answers <path>             Read the expected answers from another file when verifying
+                                 (defaults to answers.toml)
+    --src <dir>                  Generate new days in another source directory
+    --config <path>              Read settings from another file (defaults to $AOC_CONFIG or aoc.toml)
+    --log <path>                 Record submissions in another file (defaults to submissions.log)
+    --page <path>                Read the puzzle page from a file (defaults to dayNN/puzzle.html)
+    --fixtures <dir>             Read or write examples in another directory (defaults to fixtures/)
+    --interval <ms>              Poll the watched files this often (default 500)
+    --size <count>               Lines, or grid rows and columns, of a generated input (default 100)
+    --against <pair>             Shrink while the pair's implementations disagree instead of panicking
+    --expect <answer>            Shrink while the part's answer differs from this one
+    --seed <number>              Seed the generator, fuzzer or comparison to reproduce a run
+                                 (default 2023)
+
+Settings:
+    aoc.toml may set `session`, `base_url`, `input_dir`, `answers`, `format`, `jobs` and
+    `iterations`, plus per-day parameters in `[dayNN]` tables. Each setting can be overridden
+    by $AOC_<SETTING>, e.g. $AOC_JOBS, which is in turn overridden by the matching option.
+    The default aoc.toml, answers.toml, submissions.log and fixtures/ are relative to the
+    working directory, while inputs and new days default to the src/ of the crate the binary
+    was built from.
+
+Build with `--features alloc-stats` to have `run` report allocations, bytes allocated and peak
+live bytes for each part.";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Part {
+    One,
+    Two,
+}
+
+impl Part {
+    pub fn number(&self) -> u8 {
+        match self {
+            Part::One => 1,
+            Part::Two => 2,
+        }
+    }
+
+    fn from_arg(arg: &str) -> Result<Self, String> {
+        match arg {
+            "1" => Ok(Part::One),
+            "2" => Ok(Part::Two),
+            _ => Err(format!("invalid part `{arg}`, expected 1 or 2")),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DaySelection {
+    All,
+    Days(Vec<u8>),
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct RunArgs {
+    pub days: DaySelection,
+    pub parts: Vec<Part>,
+    pub input: InputSource,
+    pub quiet: bool,
+    pub format: Format,
+    pub jobs: usize,
+    pub params: Vec<(String, String)>,
+    pub trace: Option<Filter>,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct BenchArgs {
+    pub run: RunArgs,
+    pub iterations: u32,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct VerifyArgs {
+    pub run: RunArgs,
+    pub answers: PathBuf,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct NewArgs {
+    pub day: u8,
+    pub title: Option<String>,
+    pub src: PathBuf,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct FetchArgs {
+    pub days: DaySelection,
+    pub input_dir: PathBuf,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct SubmitArgs {
+    pub day: u8,
+    pub part: Part,
+    pub input: InputSource,
+    pub log: PathBuf,
+    pub params: Vec<(String, String)>,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct ExamplesArgs {
+    pub day: u8,
+    pub page: PathBuf,
+    pub fixtures: PathBuf,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct WatchArgs {
+    pub run: RunArgs,
+    pub fixtures: PathBuf,
+    pub interval: Duration,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct GenerateArgs {
+    pub day: u8,
+    pub size: usize,
+    pub seed: u64,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct FuzzArgs {
+    pub run: RunArgs,
+    pub mutations: u32,
+    pub seed: u64,
+    pub fixtures: PathBuf,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct CompareArgs {
+    pub pairs: Vec<String>,
+    pub cases: u32,
+    pub size: usize,
+    pub seed: u64,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct ShrinkArgs {
+    pub day: u8,
+    pub input: InputSource,
+    pub part: Option<Part>,
+    pub pair: Option<String>,
+    pub expected: Option<String>,
+    pub fixtures: PathBuf,
+    pub params: Vec<(String, String)>,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum Command {
+    Run(RunArgs),
+    Bench(BenchArgs),
+    Verify(VerifyArgs),
+    New(NewArgs),
+    Fetch(FetchArgs),
+    Submit(SubmitArgs),
+    Examples(ExamplesArgs),
+    Watch(WatchArgs),
+    Generate(GenerateArgs),
+    Fuzz(FuzzArgs),
+    Compare(CompareArgs),
+    Shrink(ShrinkArgs),
+    Help,
+}
+
+pub fn config_flag(args: &[String]) -> Option<PathBuf> {
+    args.iter()
+        .position(|arg| arg == "--config")
+        .and_then(|key| args.get(key + 1))
+        .map(PathBuf::from)
+}
+
+pub fn parse_args(
+    args: impl IntoIterator<Item = String>,
+    config: &Config,
+) -> Result<Command, String> {
+    let mut args = strip_config_flag(args.into_iter())?.into_iter();
+
+    match args.next().as_deref() {
+        Some("run") => parse_run_args(args, config).map(Command::Run),
+        Some("bench") => parse_bench_args(args, config).map(Command::Bench),
+        Some("verify") => parse_verify_args(args, config).map(Command::Verify),
+        Some("new") => parse_new_args(args).map(Command::New),
+        Some("fetch") => parse_fetch_args(args, config).map(Command::Fetch),
+        Some("submit") => parse_submit_args(args, config).map(Command::Submit),
+        Some("examples") => parse_examples_args(args, config).map(Command::Examples),
+        Some("watch") => parse_watch_args(args, config).map(Command::Watch),
+        Some("generate") => parse_generate_args(args).map(Command::Generate),
+        Some("fuzz") => parse_fuzz_args(args, config).map(Command::Fuzz),
+        Some("compare") => parse_compare_args(args).map(Command::Compare),
+        Some("shrink") => parse_shrink_args(args, config).map(Command::Shrink),
+        Some("help" | "--help" | "-h") | None => Ok(Command::Help),
+        Some(command) => Err(format!("unknown command `{command}`")),
+    }
+}
+
+fn strip_config_flag(mut args: impl Iterator<Item = String>) -> Result<Vec<String>, String> {
+    let mut stripped = vec![];
+
+    while let Some(arg) = args.next() {
+        if arg == "--config" {
+            args.next().ok_or("missing value for `--config`")?;
+        } else {
+            stripped.push(arg);
+        }
+    }
+    Ok(stripped)
+}
+
+fn parse_run_args(
+    mut args: impl Iterator<Item = String>,
+    config: &Config,
+) -> Result<RunArgs, String> {
+    let mut days = None;
+    let mut parts = vec![Part::One, Part::Two];
+    let mut input = None;
+    let mut quiet = false;
+    let mut format = config.format;
+    let mut jobs = config.jobs;
+    let mut params = vec![];
+    let mut verbosity = 0;
+    let mut trace_days = None;
+
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--part" | "-p" => {
+                let part = args.next().ok_or("missing value for `--part`")?;
+                parts = vec![Part::from_arg(&part)?];
+            }
+            "--input" | "-i" => {
+                let path = args.next().ok_or("missing value for `--input`")?;
+                input = Some(InputSource::from_arg(&path));
+            }
+            "--quiet" | "-q" => quiet = true,
+            "--format" | "-f" => {
+                let value = args.next().ok_or("missing value for `--format`")?;
+                format = Format::from_arg(&value)?;
+            }
+            "--jobs" | "-j" => {
+                let count = args.next().ok_or("missing value for `--jobs`")?;
+                jobs = match count.parse() {
+                    Ok(count) if count > 0 => count,
+                    _ => return Err(format!("invalid job count `{count}`")),
+                };
+            }
+            "--input-dir" => {
+                let dir = args.next().ok_or("missing value for `--input-dir`")?;
+                input = Some(InputSource::Dir(PathBuf::from(dir)));
+            }
+            "--param" => {
+                let param = args.next().ok_or("missing value for `--param`")?;
+                params.push(parse_param(&param)?);
+            }
+            "--verbose" => verbosity += 1,
+            "--trace" => {
+                let value = args.next().ok_or("missing value for `--trace`")?;
+                trace_days = Some(parse_trace_days(&value)?);
+            }
+            _ if arg
+                .strip_prefix('-')
+                .is_some_and(|rest| !rest.is_empty() && rest.bytes().all(|byte| byte == b'v')) =>
+            {
+                verbosity += arg.len() - 1
+            }
+            _ if arg.starts_with('-') => return Err(format!("unknown option `{arg}`")),
+            _ if days.is_none() => days = Some(parse_days(&arg)?),
+            _ => return Err(format!("unexpected argument `{arg}`")),
+        }
+    }
+
+    let days = days.ok_or("missing day selection, e.g. `run 7` or `run all`")?;
+    let input = input.unwrap_or_else(|| InputSource::Dir(config.input_dir.clone()));
+    if input.is_single() && !matches!(&days, DaySelection::Days(days) if days.len() == 1) {
+        return Err(String::from(
+            "`--input` can only be used when a single day is selected",
+        ));
+    }
+    let trace = match (Level::from_verbosity(verbosity), trace_days) {
+        (None, None) => None,
+        (level, days) => Some(Filter {
+            level: level.unwrap_or(Level::Debug),
+            days: days.unwrap_or(DaySelection::All),
+        }),
+    };
+
+    Ok(RunArgs {
+        days,
+        parts,
+        input,
+        quiet,
+        format,
+        jobs,
+        params,
+        trace,
+    })
+}
+
+fn parse_trace_days(s: &str) -> Result<DaySelection, String> {
+    let days: Vec<&str> = s
+        .split(',')
+        .map(|day| day.strip_prefix("day").unwrap_or(day))
+        .collect();
+    parse_days(&days.join(","))
+}
+
+fn parse_param(arg: &str) -> Result<(String, String), String> {
+    match arg.split_once('=') {
+        Some((key, value)) if !key.trim().is_empty() => {
+            Ok((key.trim().to_string(), value.trim().to_string()))
+        }
+        _ => Err(format!(
+            "invalid parameter `{arg}`, expected `<key>=<value>`"
+        )),
+    }
+}
+
+fn parse_bench_args(
+    mut args: impl Iterator<Item = String>,
+    config: &Config,
+) -> Result<BenchArgs, String> {
+    let mut iterations = config.iterations;
+    let mut run_args = vec![];
+
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--iterations" | "-n" => {
+                let count = args.next().ok_or("missing value for `--iterations`")?;
+                iterations = match count.parse() {
+                    Ok(count) if count > 0 => count,
+                    _ => return Err(format!("invalid iteration count `{count}`")),
+                };
+            }
+            _ => run_args.push(arg),
+        }
+    }
+
+    let run = parse_run_args(run_args.into_iter(), config)?;
+    if run.trace.is_some() {
+        return Err(String::from("tracing cannot be combined with `bench`"));
+    }
+    Ok(BenchArgs { run, iterations })
+}
+
+fn parse_verify_args(
+    mut args: impl Iterator<Item = String>,
+    config: &Config,
+) -> Result<VerifyArgs, String> {
+    let mut answers = config.answers.clone();
+    let mut run_args = vec![];
+
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--answers" => {
+                let path = args.next().ok_or("missing value for `--answers`")?;
+                answers = PathBuf::from(path);
+            }
+            _ => run_args.push(arg),
+        }
+    }
+
+    Ok(VerifyArgs {
+        run: parse_run_args(run_args.into_iter(), config)?,
+        answers,
+    })
+}
+
+fn parse_new_args(mut args: impl Iterator<Item = String>) -> Result<NewArgs, String> {
+    let mut day = None;
+    let mut title = None;
+    let mut src = PathBuf::from(DEFAULT_SOURCE_DIR);
+
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--title" => title = Some(args.next().ok_or("missing value for `--title`")?),
+            "--src" => src = PathBuf::from(args.next().ok_or("missing value for `--src`")?),
+            _ if arg.starts_with('-') => return Err(format!("unknown option `{arg}`")),
+            _ if day.is_none() => day = Some(parse_day(&arg)?),
+            _ => return Err(format!("unexpected argument `{arg}`")),
+        }
+    }
+
+    Ok(NewArgs {
+        day: day.ok_or("missing day, e.g. `new 12`")?,
+        title,
+        src,
+    })
+}
+
+fn parse_fetch_args(
+    mut args: impl Iterator<Item = String>,
+    config: &Config,
+) -> Result<FetchArgs, String> {
+    let mut days = None;
+    let mut input_dir = None;
+
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--input-dir" => {
+                let dir = args.next().ok_or("missing value for `--input-dir`")?;
+                input_dir = Some(PathBuf::from(dir));
+            }
+            _ if arg.starts_with('-') => return Err(format!("unknown option `{arg}`")),
+            _ if days.is_none() => days = Some(parse_days(&arg)?),
+            _ => return Err(format!("unexpected argument `{arg}`")),
+        }
+    }
+
+    Ok(FetchArgs {
+        days: days.ok_or("missing day selection, e.g. `fetch 7` or `fetch all`")?,
+        input_dir: input_dir.unwrap_or_else(|| config.input_dir.clone()),
+    })
+}
+
+fn parse_submit_args(
+    mut args: impl Iterator<Item = String>,
+    config: &Config,
+) -> Result<SubmitArgs, String> {
+    let mut positional = vec![];
+    let mut input = None;
+    let mut log = PathBuf::from(DEFAULT_LOG_FILE);
+    let mut params = vec![];
+
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--input" | "-i" => {
+                let path = args.next().ok_or("missing value for `--input`")?;
+                input = Some(InputSource::from_arg(&path));
+            }
+            "--input-dir" => {
+                let dir = args.next().ok_or("missing value for `--input-dir`")?;
+                input = Some(InputSource::Dir(PathBuf::from(dir)));
+            }
+            "--log" => log = PathBuf::from(args.next().ok_or("missing value for `--log`")?),
+            "--param" => {
+                let param = args.next().ok_or("missing value for `--param`")?;
+                params.push(parse_param(&param)?);
+            }
+            _ if arg.starts_with('-') => return Err(format!("unknown option `{arg}`")),
+            _ => positional.push(arg),
+        }
+    }
+
+    let [day, part] = &positional[..] else {
+        return Err(String::from("expected a day and a part, e.g. `submit 7 2`"));
+    };
+    Ok(SubmitArgs {
+        day: parse_day(day)?,
+        part: Part::from_arg(part)?,
+        input: input.unwrap_or_else(|| InputSource::Dir(config.input_dir.clone())),
+        log,
+        params,
+    })
+}
+
+fn parse_examples_args(
+    mut args: impl Iterator<Item = String>,
+    config: &Config,
+) -> Result<ExamplesArgs, String> {
+    let mut day = None;
+    let mut page = None;
+    let mut fixtures = PathBuf::from(DEFAULT_FIXTURES_DIR);
+
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--page" => {
+                page = Some(PathBuf::from(
+                    args.next().ok_or("missing value for `--page`")?,
+                ))
+            }
+            "--fixtures" => {
+                fixtures = PathBuf::from(args.next().ok_or("missing value for `--fixtures`")?)
+            }
+            _ if arg.starts_with('-') => return Err(format!("unknown option `{arg}`")),
+            _ if day.is_none() => day = Some(parse_day(&arg)?),
+            _ => return Err(format!("unexpected argument `{arg}`")),
+        }
+    }
+
+    let day = day.ok_or("missing day, e.g. `examples 7`")?;
+    Ok(ExamplesArgs {
+        day,
+        page: page.unwrap_or_else(|| {
+            config
+                .input_dir
+                .join(format!("day{day:02}"))
+                .join("puzzle.html")
+        }),
+        fixtures,
+    })
+}
+
+fn parse_watch_args(
+    mut args: impl Iterator<Item = String>,
+    config: &Config,
+) -> Result<WatchArgs, String> {
+    let mut fixtures = PathBuf::from(DEFAULT_FIXTURES_DIR);
+    let mut interval = DEFAULT_INTERVAL;
+    let mut run_args = vec![];
+
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--fixtures" => {
+                fixtures = PathBuf::from(args.next().ok_or("missing value for `--fixtures`")?)
+            }
+            "--interval" => {
+                let millis = args.next().ok_or("missing value for `--interval`")?;
+                interval = match millis.parse() {
+                    Ok(millis) if millis > 0 => Duration::from_millis(millis),
+                    _ => return Err(format!("invalid interval `{millis}`")),
+                };
+            }
+            _ => run_args.push(arg),
+        }
+    }
+
+    let run = parse_run_args(run_args.into_iter(), config)?;
+    if !matches!(&run.days, DaySelection::Days(days) if days.len() == 1) {
+        return Err(String::from("`watch` needs a single day, e.g. `watch 9`"));
+    }
+    if run.input == InputSource::Stdin {
+        return Err(String::from("`watch` cannot read the input from stdin"));
+    }
+    Ok(WatchArgs {
+        run,
+        fixtures,
+        interval,
+    })
+}
+
+fn parse_generate_args(mut args: impl Iterator<Item = String>) -> Result<GenerateArgs, String> {
+    let mut day = None;
+    let mut size = DEFAULT_SIZE;
+    let mut seed = DEFAULT_SEED;
+
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--size" => {
+                let count = args.next().ok_or("missing value for `--size`")?;
+                size = match count.parse() {
+                    Ok(count) if count > 0 => count,
+                    _ => return Err(format!("invalid size `{count}`")),
+                };
+            }
+            "--seed" => {
+                let number = args.next().ok_or("missing value for `--seed`")?;
+                seed = number
+                    .parse()
+                    .map_err(|_| format!("invalid seed `{number}`"))?;
+            }
+            _ if arg.starts_with('-') => return Err(format!("unknown option `{arg}`")),
+            _ if day.is_none() => day = Some(parse_day(&arg)?),
+            _ => return Err(format!("unexpected argument `{arg}`")),
+        }
+    }
+
+    Ok(GenerateArgs {
+        day: day.ok_or("missing day, e.g. `generate 7`")?,
+        size,
+        seed,
+    })
+}
+
+fn parse_fuzz_args(
+    mut args: impl Iterator<Item = String>,
+    config: &Config,
+) -> Result<FuzzArgs, String> {
+    let mut mutations = DEFAULT_MUTATIONS;
+    let mut seed = DEFAULT_SEED;
+    let mut fixtures = PathBuf::from(DEFAULT_FIXTURES_DIR);
+    let mut run_args = vec![];
+
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--iterations" | "-n" => {
+                let count = args.next().ok_or("missing value for `--iterations`")?;
+                mutations = match count.parse() {
+                    Ok(count) if count > 0 => count,
+                    _ => return Err(format!("invalid iteration count `{count}`")),
+                };
+            }
+            "--seed" => {
+                let number = args.next().ok_or("missing value for `--seed`")?;
+                seed = number
+                    .parse()
+                    .map_err(|_| format!("invalid seed `{number}`"))?;
+            }
+            "--fixtures" => {
+                fixtures = PathBuf::from(args.next().ok_or("missing value for `--fixtures`")?)
+            }
+            _ => run_args.push(arg),
+        }
+    }
+
+    let run = parse_run_args(run_args.into_iter(), config)?;
+    if run.trace.is_some() {
+        return Err(String::from("tracing cannot be combined with `fuzz`"));
+    }
+    Ok(FuzzArgs {
+        run,
+        mutations,
+        seed,
+        fixtures,
+    })
+}
+
+fn parse_compare_args(mut args: impl Iterator<Item = String>) -> Result<CompareArgs, String> {
+    let mut pairs = vec![];
+    let mut cases = DEFAULT_CASES;
+    let mut size = DEFAULT_SIZE;
+    let mut seed = DEFAULT_SEED;
+
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--iterations" | "-n" => {
+                let count = args.next().ok_or("missing value for `--iterations`")?;
+                cases = match count.parse() {
+                    Ok(count) if count > 0 => count,
+                    _ => return Err(format!("invalid iteration count `{count}`")),
+                };
+            }
+            "--size" => {
+                let count = args.next().ok_or("missing value for `--size`")?;
+                size = match count.parse() {
+                    Ok(count) if count > 0 => count,
+                    _ => return Err(format!("invalid size `{count}`")),
+                };
+            }
+            "--seed" => {
+                let number = args.next().ok_or("missing value for `--seed`")?;
+                seed = number
+                    .parse()
+                    .map_err(|_| format!("invalid seed `{number}`"))?;
+            }
+            _ if arg.starts_with('-') => return Err(format!("unknown option `{arg}`")),
+            _ => {
+                for name in arg.split(',') {
+                    if differential::get(name).is_none() {
+                        let names: Vec<&str> =
+                            differential::PAIRS.iter().map(|pair| pair.name).collect();
+                        return Err(format!(
+                            "unknown pair `{name}`, expected one of {}",
+                            names.join(", ")
+                        ));
+                    }
+                    pairs.push(name.to_string());
+                }
+            }
+        }
+    }
+
+    Ok(CompareArgs {
+        pairs,
+        cases,
+        size,
+        seed,
+    })
+}
+
+fn parse_shrink_args(
+    mut args: impl Iterator<Item = String>,
+    config: &Config,
+) -> Result<ShrinkArgs, String> {
+    let mut day = None;
+    let mut input = None;
+    let mut part = None;
+    let mut pair = None;
+    let mut expected = None;
+    let mut fixtures = PathBuf::from(DEFAULT_FIXTURES_DIR);
+    let mut params = vec![];
+
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--input" | "-i" => {
+                let path = args.next().ok_or("missing value for `--input`")?;
+                input = Some(InputSource::from_arg(&path));
+            }
+            "--part" | "-p" => {
+                let value = args.next().ok_or("missing value for `--part`")?;
+                part = Some(Part::from_arg(&value)?);
+            }
+            "--against" => pair = Some(args.next().ok_or("missing value for `--against`")?),
+            "--expect" => expected = Some(args.next().ok_or("missing value for `--expect`")?),
+            "--fixtures" => {
+                fixtures = PathBuf::from(args.next().ok_or("missing value for `--fixtures`")?)
+            }
+            "--param" => {
+                let param = args.next().ok_or("missing value for `--param`")?;
+                params.push(parse_param(&param)?);
+            }
+            _ if arg.starts_with('-') => return Err(format!("unknown option `{arg}`")),
+            _ if day.is_none() => day = Some(parse_day(&arg)?),
+            _ => return Err(format!("unexpected argument `{arg}`")),
+        }
+    }
+
+    let day = day.ok_or("missing day, e.g. `shrink 7 --input big.txt`")?;
+    if expected.is_some() && (pair.is_some() || part.is_none()) {
+        return Err(String::from(
+            "`--expect` needs `--part` and cannot be combined with `--against`",
+        ));
+    }
+    if let Some(name) = &pair {
+        match differential::get(name) {
+            Some(found) if found.day == day => (),
+            Some(found) => return Err(format!("pair `{name}` is for day {}", found.day)),
+            None => return Err(format!("unknown pair `{name}`")),
+        }
+        if part.is_some() {
+            return Err(String::from("`--part` cannot be combined with `--against`"));
+        }
+    }
+    Ok(ShrinkArgs {
+        day,
+        input: input.unwrap_or_else(|| InputSource::Dir(config.input_dir.clone())),
+        part,
+        pair,
+        expected,
+        fixtures,
+        params,
+    })
+}
+
+pub fn parse_days(s: &str) -> Result<DaySelection, String> {
+    if s == "all" {
+        return Ok(DaySelection::All);
+    }
+
+    let mut days: Vec<u8> = vec![];
+
+    for selection in s.split(',') {
+        days.extend(parse_day_range(selection.trim())?);
+    }
+    days.sort_unstable();
+    days.dedup();
+    Ok(DaySelection::Days(days))
+}
+
+fn parse_day_range(s: &str) -> Result<RangeInclusive<u8>, String> {
+    let range = if let Some((start, end)) = s.split_once("..=") {
+        parse_day(start)?..=parse_day(end)?
+    } else if let Some((start, end)) = s.split_once("..") {
+        let end = parse_day(end)?;
+        if end == FIRST_DAY {
+            return Err(format!("day range `{s}` is empty"));
+        }
+        parse_day(start)?..=end - 1
+    } else {
+        let day = parse_day(s)?;
+        day..=day
+    };
+
+    if range.is_empty() {
+        return Err(format!("day range `{s}` is empty"));
+    }
+    Ok(range)
+}
+
+fn parse_day(s: &str) -> Result<u8, String> {
+    match s.parse::<u8>() {
+        Ok(day) if (FIRST_DAY..=LAST_DAY).contains(&day) => Ok(day),
+        _ => Err(format!(
+            "invalid day `{s}`, expected a number from {FIRST_DAY} to {LAST_DAY}"
+        )),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{path::PathBuf, time::Duration};
+
+    use crate::{
+        config::Config,
+        fixtures::DEFAULT_FIXTURES_DIR,
+        input::InputSource,
+        report::Format,
+        trace::{Filter, Level},
+    };
+
+    use super::{
+        config_flag, parse_args, parse_days, BenchArgs, Command, CompareArgs, DaySelection,
+        ExamplesArgs, FetchArgs, FuzzArgs, GenerateArgs, NewArgs, Part, RunArgs, ShrinkArgs,
+        SubmitArgs, VerifyArgs, WatchArgs,
+    };
+
+    fn parse(s: &str) -> Result<Command, String> {
+        parse_args(s.split_whitespace().map(String::from), &Config::default())
+    }
+
+    #[test]
+    fn day_selections_are_parsed() {
+        assert_eq!(parse_days("7"), Ok(DaySelection::Days(vec![7])));
+        assert_eq!(
+            parse_days("3..=6"),
+            Ok(DaySelection::Days(vec![3, 4, 5, 6]))
+        );
+        assert_eq!(parse_days("3..6"), Ok(DaySelection::Days(vec![3, 4, 5])));
+        assert_eq!(
+            parse_days("9,1..=2,2"),
+            Ok(DaySelection::Days(vec![1, 2, 9]))
+        );
+        assert_eq!(parse_days("all"), Ok(DaySelection::All));
+        assert!(parse_days("0").is_err());
+        assert!(parse_days("26").is_err());
+        assert!(parse_days("6..=3").is_err());
+        assert!(parse_days("1..1").is_err());
+        assert!(parse_days("seven").is_err());
+    }
+
+    #[test]
+    fn run_command_is_parsed() {
+        assert_eq!(
+            parse("run 7 --part 2 --input - -q"),
+            Ok(Command::Run(RunArgs {
+                days: DaySelection::Days(vec![7]),
+                parts: vec![Part::Two],
+                input: InputSource::Stdin,
+                quiet: true,
+                format: Format::Text,
+                jobs: 1,
+                params: vec![],
+                trace: None,
+            }))
+        );
+        assert_eq!(
+            parse("run all --input-dir inputs --format json -j 4 --param red=20 --param start=BBB"),
+            Ok(Command::Run(RunArgs {
+                days: DaySelection::All,
+                parts: vec![Part::One, Part::Two],
+                input: InputSource::Dir(PathBuf::from("inputs")),
+                quiet: false,
+                format: Format::Json,
+                jobs: 4,
+                params: vec![
+                    (String::from("red"), String::from("20")),
+                    (String::from("start"), String::from("BBB"))
+                ],
+                trace: None,
+            }))
+        );
+        assert_eq!(parse(""), Ok(Command::Help));
+        assert!(parse("run").is_err());
+        assert!(parse("run 7 --part 3").is_err());
+        assert!(parse("run 7 8").is_err());
+        assert!(parse("fly 7").is_err());
+        assert!(parse("run 1..=2 --input my.txt").is_err());
+        assert!(parse("run 7 --format xml").is_err());
+        assert!(parse("run all --jobs 0").is_err());
+        assert!(parse("run all --param red").is_err());
+        assert!(parse("run all --param =20").is_err());
+    }
+
+    #[test]
+    fn trace_options_are_parsed() {
+        let trace = |s| match parse(s) {
+            Ok(Command::Run(args)) => args.trace,
+            other => panic!("{other:?}"),
+        };
+
+        assert_eq!(trace("run all"), None);
+        assert_eq!(
+            trace("run all -v"),
+            Some(Filter {
+                level: Level::Info,
+                days: DaySelection::All,
+            })
+        );
+        assert_eq!(
+            trace("run 5..=7 -vv --verbose --trace day07,5"),
+            Some(Filter {
+                level: Level::Trace,
+                days: DaySelection::Days(vec![5, 7]),
+            })
+        );
+        assert_eq!(
+            trace("run 7 --trace day07"),
+            Some(Filter {
+                level: Level::Debug,
+                days: DaySelection::Days(vec![7]),
+            })
+        );
+        assert!(parse("run 7 --trace day26").is_err());
+        assert!(parse("run 7 -vx").is_err());
+        assert!(parse("run 7 xv").is_err());
+        assert!(parse("run é").is_err());
+        assert!(parse("run 7 -é").is_err());
+        assert!(parse("bench 7 -v").is_err());
+        assert!(parse("fuzz 7 --trace 7").is_err());
+        assert!(matches!(
+            parse("verify 7 -vvv"),
+            Ok(Command::Verify(args)) if args.run.trace.is_some()
+        ));
+    }
+
+    #[test]
+    fn bench_command_is_parsed() {
+        assert_eq!(
+            parse("bench 6 -n 50 --part 1"),
+            Ok(Command::Bench(BenchArgs {
+                run: RunArgs {
+                    days: DaySelection::Days(vec![6]),
+                    parts: vec![Part::One],
+                    input: InputSource::Dir(Config::default().input_dir),
+                    quiet: false,
+                    format: Format::Text,
+                    jobs: 1,
+                    params: vec![],
+                    trace: None,
+                },
+                iterations: 50,
+            }))
+        );
+        assert!(matches!(
+            parse("bench all"),
+            Ok(Command::Bench(BenchArgs { iterations: 10, .. }))
+        ));
+        assert!(parse("bench 6 -n 0").is_err());
+        assert!(parse("bench 6 --iterations").is_err());
+    }
+
+    #[test]
+    fn verify_command_is_parsed() {
+        assert_eq!(
+            parse("verify all --answers old.toml -q"),
+            Ok(Command::Verify(VerifyArgs {
+                run: RunArgs {
+                    days: DaySelection::All,
+                    parts: vec![Part::One, Part::Two],
+                    input: InputSource::Dir(Config::default().input_dir),
+                    quiet: true,
+                    format: Format::Text,
+                    jobs: 1,
+                    params: vec![],
+                    trace: None,
+                },
+                answers: PathBuf::from("old.toml"),
+            }))
+        );
+        assert!(parse("verify").is_err());
+        assert!(parse("verify all --answers").is_err());
+    }
+
+    #[test]
+    fn new_command_is_parsed() {
+        assert_eq!(
+            parse("new 12 --src /tmp/src --title Springs"),
+            Ok(Command::New(NewArgs {
+                day: 12,
+                title: Some(String::from("Springs")),
+                src: PathBuf::from("/tmp/src"),
+            }))
+        );
+        assert!(parse("new").is_err());
+        assert!(parse("new 26").is_err());
+        assert!(parse("new 12 13").is_err());
+    }
+
+    #[test]
+    fn fetch_command_is_parsed() {
+        assert_eq!(
+            parse("fetch 1..=3 --input-dir inputs --config my.toml"),
+            Ok(Command::Fetch(FetchArgs {
+                days: DaySelection::Days(vec![1, 2, 3]),
+                input_dir: PathBuf::from("inputs"),
+            }))
+        );
+        assert!(parse("fetch").is_err());
+        assert!(parse("fetch 7 --part 1").is_err());
+    }
+
+    #[test]
+    fn submit_command_is_parsed() {
+        assert_eq!(
+            parse("submit 11 2 --input - --log my.log --param part2_expansion=10"),
+            Ok(Command::Submit(SubmitArgs {
+                day: 11,
+                part: Part::Two,
+                input: InputSource::Stdin,
+                log: PathBuf::from("my.log"),
+                params: vec![(String::from("part2_expansion"), String::from("10"))],
+            }))
+        );
+        assert!(parse("submit 7").is_err());
+        assert!(parse("submit 7 3").is_err());
+        assert!(parse("submit 7 1 2").is_err());
+    }
+
+    #[test]
+    fn examples_command_is_parsed() {
+        assert_eq!(
+            parse("examples 7 --page page.html --fixtures out"),
+            Ok(Command::Examples(ExamplesArgs {
+                day: 7,
+                page: PathBuf::from("page.html"),
+                fixtures: PathBuf::from("out"),
+            }))
+        );
+        assert!(parse("examples").is_err());
+    }
+
+    #[test]
+    fn watch_command_is_parsed() {
+        assert_eq!(
+            parse("watch 9 --part 2 --interval 100 --fixtures examples"),
+            Ok(Command::Watch(WatchArgs {
+                run: RunArgs {
+                    days: DaySelection::Days(vec![9]),
+                    parts: vec![Part::Two],
+                    input: InputSource::Dir(Config::default().input_dir),
+                    quiet: false,
+                    format: Format::Text,
+                    jobs: 1,
+                    params: vec![],
+                    trace: None,
+                },
+                fixtures: PathBuf::from("examples"),
+                interval: Duration::from_millis(100),
+            }))
+        );
+        assert!(parse("watch all").is_err());
+        assert!(parse("watch 1..=2").is_err());
+        assert!(parse("watch 9 --input -").is_err());
+        assert!(parse("watch 9 --interval 0").is_err());
+    }
+
+    #[test]
+    fn compare_command_is_parsed() {
+        assert_eq!(
+            parse("compare day01-calibration,day11-expansion -n 50 --size 20 --seed 3"),
+            Ok(Command::Compare(CompareArgs {
+                pairs: vec![
+                    String::from("day01-calibration"),
+                    String::from("day11-expansion")
+                ],
+                cases: 50,
+                size: 20,
+                seed: 3,
+            }))
+        );
+        assert!(matches!(parse("compare"), Ok(Command::Compare(args)) if args.pairs.is_empty()));
+        assert!(parse("compare day07-ranks").is_err());
+        assert!(parse("compare -n 0").is_err());
+    }
+
+    #[test]
+    fn shrink_command_is_parsed() {
+        assert_eq!(
+            parse("shrink 6 -i big.txt --against day06-margin --fixtures out"),
+            Ok(Command::Shrink(ShrinkArgs {
+                day: 6,
+                input: InputSource::File(PathBuf::from("big.txt")),
+                part: None,
+                pair: Some(String::from("day06-margin")),
+                expected: None,
+                fixtures: PathBuf::from("out"),
+                params: vec![],
+            }))
+        );
+        assert!(matches!(
+            parse("shrink 9 --part 2"),
+            Ok(Command::Shrink(ShrinkArgs {
+                part: Some(Part::Two),
+                pair: None,
+                ..
+            }))
+        ));
+        assert_eq!(
+            parse("shrink 11 -p 1 --expect 374 --param part1_expansion=2"),
+            Ok(Command::Shrink(ShrinkArgs {
+                day: 11,
+                input: InputSource::Dir(Config::default().input_dir),
+                part: Some(Part::One),
+                pair: None,
+                expected: Some(String::from("374")),
+                fixtures: PathBuf::from(DEFAULT_FIXTURES_DIR),
+                params: vec![(String::from("part1_expansion"), String::from("2"))],
+            }))
+        );
+        assert!(parse("shrink 11 --expect 374").is_err());
+        assert!(parse("shrink 6 --against day06-margin --expect 4").is_err());
+        assert!(parse("shrink").is_err());
+        assert!(parse("shrink 7 --against day06-margin").is_err());
+        assert!(parse("shrink 6 --against day06-margin --part 1").is_err());
+    }
+
+    #[test]
+    fn generate_command_is_parsed() {
+        assert_eq!(
+            parse("generate 10 --size 140 --seed 7"),
+            Ok(Command::Generate(GenerateArgs {
+                day: 10,
+                size: 140,
+                seed: 7,
+            }))
+        );
+        assert!(matches!(parse("generate 3"), Ok(Command::Generate(_))));
+        assert!(parse("generate").is_err());
+        assert!(parse("generate 3 --size 0").is_err());
+        assert!(parse("generate 3 --seed -1").is_err());
+    }
+
+    #[test]
+    fn fuzz_command_is_parsed() {
+        assert_eq!(
+            parse("fuzz 4,6 -n 50 --seed 9 --fixtures out"),
+            Ok(Command::Fuzz(FuzzArgs {
+                run: RunArgs {
+                    days: DaySelection::Days(vec![4, 6]),
+                    parts: vec![Part::One, Part::Two],
+                    input: InputSource::Dir(Config::default().input_dir),
+                    quiet: false,
+                    format: Format::Text,
+                    jobs: 1,
+                    params: vec![],
+                    trace: None,
+                },
+                mutations: 50,
+                seed: 9,
+                fixtures: PathBuf::from("out"),
+            }))
+        );
+        assert!(parse("fuzz").is_err());
+        assert!(parse("fuzz all -n 0").is_err());
+    }
+
+    #[test]
+    fn config_supplies_defaults() {
+        let config = Config {
+            input_dir: PathBuf::from("inputs"),
+            format: Format::Csv,
+            jobs: 3,
+            iterations: 25,
+            ..Config::default()
+        };
+        let args: Vec<String> = ["bench", "7", "--config", "my.toml", "-j", "2"]
+            .map(String::from)
+            .into();
+
+        assert_eq!(config_flag(&args), Some(PathBuf::from("my.toml")));
+        assert_eq!(
+            parse_args(args, &config),
+            Ok(Command::Bench(BenchArgs {
+                run: RunArgs {
+                    days: DaySelection::Days(vec![7]),
+                    parts: vec![Part::One, Part::Two],
+                    input: InputSource::Dir(PathBuf::from("inputs")),
+                    quiet: false,
+                    format: Format::Csv,
+                    jobs: 2,
+                    params: vec![],
+                    trace: None,
+                },
+                iterations: 25,
+            }))
+        );
+        assert!(parse("run 7 --config").is_err());
+    }
+}
@@ -0,0 +1,213 @@
+use std::{
+    fs,
+    path::Path,
+    thread,
+    time::{Duration, Instant},
+};
+
+use crate::{
+    cli::Part,
+    config::Config,
+    error::{AocError, Result},
+    http::Request,
+    solution::Answer,
+    submit::Verdict,
+};
+
+pub const SESSION_VAR: &str = "AOC_SESSION";
+pub const BASE_URL_VAR: &str = "AOC_BASE_URL";
+pub const DEFAULT_BASE_URL: &str = "https://adventofcode.com";
+pub const USER_AGENT: &str = concat!(
+    "aoc2023/",
+    env!("CARGO_PKG_VERSION"),
+    " (+https://github.com/kmillot/aoc2023)"
+);
+pub const YEAR: u16 = 2023;
+
+const MIN_INTERVAL: Duration = Duration::from_secs(3);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FetchOutcome {
+    Cached,
+    Downloaded,
+}
+
+#[derive(Debug)]
+pub struct Client {
+    base_url: String,
+    session: String,
+    min_interval: Duration,
+    last_request: Option<Instant>,
+}
+
+impl Client {
+    pub fn new(base_url: impl Into<String>, session: impl Into<String>) -> Self {
+        Client {
+            base_url: base_url.into().trim_end_matches('/').to_string(),
+            session: session.into(),
+            min_interval: MIN_INTERVAL,
+            last_request: None,
+        }
+    }
+
+    pub fn from_config(config: &Config) -> Result<Self> {
+        let session = config.session.as_deref().ok_or_else(|| {
+            AocError::Http(format!(
+                "no session token, set ${SESSION_VAR} or `session` in aoc.toml"
+            ))
+        })?;
+        Ok(Self::new(&config.base_url, session))
+    }
+
+    pub fn with_min_interval(mut self, min_interval: Duration) -> Self {
+        self.min_interval = min_interval;
+        self
+    }
+
+    pub fn fetch_input(&mut self, day: u8, path: &Path) -> Result<FetchOutcome> {
+        if fs::metadata(path).is_ok_and(|metadata| metadata.len() > 0) {
+            return Ok(FetchOutcome::Cached);
+        }
+
+        let url = format!("{}/{YEAR}/day/{day}/input", self.base_url);
+        self.throttle();
+        let response = self.request(Request::get(&url)).send()?;
+        if response.status != 200 {
+            return Err(AocError::Http(format!(
+                "GET {url} returned {}: {}",
+                response.status,
+                response.body.trim()
+            )));
+        }
+
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        fs::write(path, response.body)?;
+        Ok(FetchOutcome::Downloaded)
+    }
+
+    pub fn submit_answer(&mut self, day: u8, part: Part, answer: &Answer) -> Result<Verdict> {
+        let url = format!("{}/{YEAR}/day/{day}/answer", self.base_url);
+        let body = format!(
+            "level={}&answer={}",
+            part.number(),
+            url_encode(&answer.to_string())
+        );
+
+        self.throttle();
+        let response = self.request(Request::post_form(&url, body)).send()?;
+        if response.status != 200 {
+            return Err(AocError::Http(format!(
+                "POST {url} returned {}: {}",
+                response.status,
+                response.body.trim()
+            )));
+        }
+        Ok(Verdict::from_response(&response.body))
+    }
+
+    fn request<'a>(&self, request: Request<'a>) -> Request<'a> {
+        request
+            .header("User-Agent", USER_AGENT)
+            .header("Cookie", format!("session={}", self.session))
+    }
+
+    fn throttle(&mut self) {
+        if let Some(elapsed) = self.last_request.map(|last| last.elapsed()) {
+            if elapsed < self.min_interval {
+                thread::sleep(self.min_interval - elapsed);
+            }
+        }
+        self.last_request = Some(Instant::now());
+    }
+}
+
+fn url_encode(s: &str) -> String {
+    s.bytes()
+        .map(|byte| match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'_' | b'.' | b'~' => {
+                (byte as char).to_string()
+            }
+            _ => format!("%{byte:02X}"),
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{fs, time::Duration};
+
+    use crate::{cli::Part, config::Config, http::mock, solution::Answer, submit::Verdict};
+
+    use super::{url_encode, Client, FetchOutcome, USER_AGENT};
+
+    #[test]
+    fn inputs_are_downloaded_once_and_cached() {
+        let dir = std::env::temp_dir().join(format!("aoc2023-fetch-{}", std::process::id()));
+        let path = dir.join("day07").join("input.txt");
+        let (url, requests) = mock::serve(vec![mock::response(200, "32T3K 765\n")]);
+        let mut client = Client::new(url, "abc").with_min_interval(Duration::ZERO);
+
+        assert_eq!(
+            client.fetch_input(7, &path).unwrap(),
+            FetchOutcome::Downloaded
+        );
+        assert_eq!(client.fetch_input(7, &path).unwrap(), FetchOutcome::Cached);
+        assert_eq!(fs::read_to_string(&path).unwrap(), "32T3K 765\n");
+
+        let request = requests.recv().unwrap();
+        assert!(request.starts_with("GET /2023/day/7/input HTTP/1.1\r\n"));
+        assert!(request.contains("Cookie: session=abc\r\n"));
+        assert!(request.contains(&format!("User-Agent: {USER_AGENT}\r\n")));
+        assert!(requests.try_recv().is_err());
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn failed_downloads_are_not_cached() {
+        let dir = std::env::temp_dir().join(format!("aoc2023-fetch-err-{}", std::process::id()));
+        let path = dir.join("day25").join("input.txt");
+        let (url, _requests) = mock::serve(vec![mock::response(
+            404,
+            "Please don't repeatedly request this endpoint before it unlocks!",
+        )]);
+        let mut client = Client::new(url, "abc");
+
+        let err = client.fetch_input(25, &path).unwrap_err();
+        assert!(err.to_string().contains("returned 404"));
+        assert!(!path.exists());
+        assert!(Client::from_config(&Config::default()).is_err());
+    }
+
+    #[test]
+    fn answers_are_posted_and_classified() {
+        let (url, requests) = mock::serve(vec![
+            mock::response(
+                200,
+                "<p>That's not the right answer; your answer is too low.</p>",
+            ),
+            mock::response(200, "<p>That's the right answer!</p>"),
+        ]);
+        let mut client = Client::new(url, "abc").with_min_interval(Duration::ZERO);
+
+        assert_eq!(
+            client
+                .submit_answer(6, Part::Two, &Answer::Integer(12))
+                .unwrap(),
+            Verdict::TooLow
+        );
+        assert_eq!(
+            client
+                .submit_answer(6, Part::Two, &Answer::Integer(46173809))
+                .unwrap(),
+            Verdict::Correct
+        );
+
+        let request = requests.recv().unwrap();
+        assert!(request.starts_with("POST /2023/day/6/answer HTTP/1.1\r\n"));
+        assert!(request.ends_with("\r\n\r\nlevel=2&answer=12"));
+        assert_eq!(url_encode("a b&c"), "a%20b%26c");
+    }
+}
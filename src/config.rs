@@ -0,0 +1,240 @@
+use std::{
+    collections::BTreeMap,
+    fs, io,
+    path::{Path, PathBuf},
+};
+
+use crate::{
+    answers::DEFAULT_ANSWERS_FILE,
+    cli::{DEFAULT_ITERATIONS, FIRST_DAY, LAST_DAY},
+    client::{BASE_URL_VAR, DEFAULT_BASE_URL, SESSION_VAR},
+    error::{AocError, Result},
+    input::{DEFAULT_INPUT_DIR, INPUT_DIR_VAR},
+    params::Params,
+    report::Format,
+    solution::Solution,
+    toml::{Document, Table, Value},
+};
+
+pub const CONFIG_VAR: &str = "AOC_CONFIG";
+pub const DEFAULT_CONFIG_FILE: &str = "aoc.toml";
+
+const ENV_VARS: &[(&str, &str)] = &[
+    (SESSION_VAR, "session"),
+    (BASE_URL_VAR, "base_url"),
+    (INPUT_DIR_VAR, "input_dir"),
+    ("AOC_ANSWERS", "answers"),
+    ("AOC_FORMAT", "format"),
+    ("AOC_JOBS", "jobs"),
+    ("AOC_ITERATIONS", "iterations"),
+];
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Config {
+    pub session: Option<String>,
+    pub base_url: String,
+    pub input_dir: PathBuf,
+    pub answers: PathBuf,
+    pub format: Format,
+    pub jobs: usize,
+    pub iterations: u32,
+    pub days: BTreeMap<u8, Table>,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Config {
+            session: None,
+            base_url: String::from(DEFAULT_BASE_URL),
+            input_dir: PathBuf::from(DEFAULT_INPUT_DIR),
+            answers: PathBuf::from(DEFAULT_ANSWERS_FILE),
+            format: Format::Text,
+            jobs: 1,
+            iterations: DEFAULT_ITERATIONS,
+            days: BTreeMap::new(),
+        }
+    }
+}
+
+impl Config {
+    pub fn parse(input: &str, base_dir: &Path) -> Result<Self> {
+        let document = Document::parse(input)?;
+        let mut config = Config::default();
+
+        for (key, value) in document.root().into_iter().flatten() {
+            config.set(key, value, base_dir)?;
+        }
+        for (name, table) in document.tables() {
+            let day = name
+                .strip_prefix("day")
+                .and_then(|day| day.parse::<u8>().ok())
+                .filter(|day| (FIRST_DAY..=LAST_DAY).contains(day))
+                .ok_or_else(|| AocError::parse(format!("unknown table `[{name}]`")))?;
+            config.days.insert(day, table.clone());
+        }
+        Ok(config)
+    }
+
+    pub fn load(path: &Path) -> Result<Self> {
+        match fs::read_to_string(path) {
+            Ok(contents) => Self::parse(&contents, path.parent().unwrap_or(Path::new(""))),
+            Err(err) if err.kind() == io::ErrorKind::NotFound => Ok(Self::default()),
+            Err(err) => Err(err.into()),
+        }
+    }
+
+    pub fn with_env(mut self, var: impl Fn(&str) -> Option<String>) -> Result<Self> {
+        for (name, key) in ENV_VARS {
+            if let Some(value) = var(name) {
+                self.set(key, &Value::String(value), Path::new(""))
+                    .map_err(|err| AocError::parse(format!("${name}: {err}")))?;
+            }
+        }
+        Ok(self)
+    }
+
+    pub fn params(
+        &self,
+        solution: &dyn Solution,
+        overrides: &[(String, String)],
+    ) -> Result<Params> {
+        let day = solution.day();
+        let mut params = Params::new(solution.params());
+
+        for (key, value) in self.days.get(&day).into_iter().flatten() {
+            params
+                .set(key, value.clone())
+                .map_err(|err| AocError::parse(format!("`[day{day:02}]`: {err}")))?;
+        }
+        for (key, value) in overrides {
+            if params.declares(key) {
+                params.set(key, Value::String(value.clone()))?;
+            }
+        }
+        Ok(params)
+    }
+
+    fn set(&mut self, key: &str, value: &Value, base_dir: &Path) -> Result<()> {
+        match key {
+            "session" => self.session = Some(string(key, value)?.trim().to_string()),
+            "base_url" => self.base_url = string(key, value)?.to_string(),
+            "input_dir" => self.input_dir = base_dir.join(string(key, value)?),
+            "answers" => self.answers = base_dir.join(string(key, value)?),
+            "format" => {
+                self.format = Format::from_arg(string(key, value)?).map_err(AocError::parse)?
+            }
+            "jobs" => self.jobs = count(key, value)? as usize,
+            "iterations" => self.iterations = count(key, value)?,
+            _ => return Err(AocError::parse(format!("unknown setting `{key}`"))),
+        }
+        Ok(())
+    }
+}
+
+pub fn config_path(flag: Option<PathBuf>) -> PathBuf {
+    flag.or_else(|| std::env::var_os(CONFIG_VAR).map(PathBuf::from))
+        .unwrap_or_else(|| PathBuf::from(DEFAULT_CONFIG_FILE))
+}
+
+fn string<'a>(key: &str, value: &'a Value) -> Result<&'a str> {
+    match value {
+        Value::String(value) => Ok(value),
+        _ => Err(AocError::parse(format!("`{key}` must be a string"))),
+    }
+}
+
+fn count(key: &str, value: &Value) -> Result<u32> {
+    let count = match value {
+        Value::Integer(value) => u32::try_from(*value).ok(),
+        Value::String(value) => value.trim().parse().ok(),
+        Value::Boolean(_) => None,
+    };
+    count
+        .filter(|count| *count > 0)
+        .ok_or_else(|| AocError::parse(format!("`{key}` must be a positive number, got `{value}`")))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::{Path, PathBuf};
+
+    use crate::{day02::Day02, day11::Day11, report::Format};
+
+    use super::Config;
+
+    const CONFIG: &str = r#"
+session = "abc"
+input_dir = "inputs"
+answers = "/tmp/answers.toml"
+format = "json"
+jobs = 4
+
+[day11]
+part2_expansion = 10
+"#;
+
+    #[test]
+    fn settings_are_read_relative_to_the_config() {
+        let config = Config::parse(CONFIG, Path::new("/home/aoc")).unwrap();
+
+        assert_eq!(config.session.as_deref(), Some("abc"));
+        assert_eq!(config.input_dir, PathBuf::from("/home/aoc/inputs"));
+        assert_eq!(config.answers, PathBuf::from("/tmp/answers.toml"));
+        assert_eq!(config.format, Format::Json);
+        assert_eq!(config.jobs, 4);
+        assert_eq!(config.iterations, Config::default().iterations);
+
+        assert!(Config::parse("jobs = 0", Path::new("")).is_err());
+        assert!(Config::parse("format = \"xml\"", Path::new("")).is_err());
+        assert!(Config::parse("threads = 4", Path::new("")).is_err());
+        assert!(Config::parse("[day26]\nx = 1", Path::new("")).is_err());
+        assert!(Config::load(Path::new("/nonexistent/aoc.toml")).is_ok());
+    }
+
+    #[test]
+    fn environment_overrides_the_config() {
+        let config = Config::parse(CONFIG, Path::new(""))
+            .unwrap()
+            .with_env(|name| match name {
+                "AOC_SESSION" => Some(String::from("xyz\n")),
+                "AOC_JOBS" => Some(String::from("2")),
+                "AOC_INPUT_DIR" => Some(String::from("other")),
+                _ => None,
+            })
+            .unwrap();
+
+        assert_eq!(config.session.as_deref(), Some("xyz"));
+        assert_eq!(config.jobs, 2);
+        assert_eq!(config.input_dir, PathBuf::from("other"));
+        assert_eq!(config.format, Format::Json);
+
+        let err = Config::default()
+            .with_env(|name| (name == "AOC_FORMAT").then(|| String::from("xml")))
+            .unwrap_err();
+        assert!(err.to_string().starts_with("$AOC_FORMAT: "));
+    }
+
+    #[test]
+    fn day_parameters_are_layered() {
+        let config = Config::parse(CONFIG, Path::new("")).unwrap();
+        let overrides = [
+            (String::from("part1_expansion"), String::from("3")),
+            (String::from("red"), String::from("20")),
+        ];
+
+        let params = config.params(&Day11, &[]).unwrap();
+        assert_eq!(params.get_u64("part1_expansion").unwrap(), 2);
+        assert_eq!(params.get_u64("part2_expansion").unwrap(), 10);
+        let params = config.params(&Day11, &overrides).unwrap();
+        assert_eq!(params.get_u64("part1_expansion").unwrap(), 3);
+        let params = config.params(&Day02, &overrides).unwrap();
+        assert_eq!(params.get_u64("red").unwrap(), 20);
+        assert_eq!(params.get_u64("green").unwrap(), 13);
+
+        let config = Config::parse("[day11]\nfoo = 1", Path::new("")).unwrap();
+        let err = config.params(&Day11, &[]).unwrap_err();
+        assert!(err
+            .to_string()
+            .starts_with("`[day11]`: unknown parameter `foo`"));
+    }
+}
@@ -1,30 +1,63 @@
-const INPUT: &str = include_str!("./input.txt");
+use crate::{
+    error::Result,
+    params::Params,
+    solution::{Solution, Solver},
+    trace::trace,
+    utils::{numbered_lines, Line},
+};
 
-type FoundNumber<'a> = (usize, &'a str);
+pub type FoundNumber<'a> = (usize, &'a str);
 
-pub fn part_one() {
-    let sum: u64 = INPUT.lines().map(get_calibration_number_legacy).sum();
-    println!("{}", sum);
+pub struct Day01;
+
+impl Solution for Day01 {
+    fn day(&self) -> u8 {
+        1
+    }
+
+    fn title(&self) -> &'static str {
+        "Trebuchet?!"
+    }
+
+    fn parse_part_one<'a>(&self, input: &'a str, _params: &Params) -> Result<Solver<'a>> {
+        let lines: Vec<Line> = numbered_lines(input).collect();
+
+        Ok(Box::new(move || {
+            let sum: u64 = get_calibration_sum(&lines, get_calibration_number_legacy)?;
+            Ok(sum.into())
+        }))
+    }
+
+    fn parse_part_two<'a>(&self, input: &'a str, _params: &Params) -> Result<Option<Solver<'a>>> {
+        let lines: Vec<Line> = numbered_lines(input).collect();
+
+        Ok(Some(Box::new(move || {
+            let sum: u64 = get_calibration_sum(&lines, get_calibration_number)?;
+            Ok(sum.into())
+        })))
+    }
 }
 
-pub fn part_two() {
-    let sum: u64 = INPUT.lines().map(get_calibration_number).sum();
-    println!("{}", sum);
+pub fn get_calibration_sum(lines: &[Line], get_number: fn(&str) -> Option<u64>) -> Result<u64> {
+    lines
+        .iter()
+        .map(|line| {
+            let number = get_number(line.text);
+            trace!(Debug, "line {}: `{}` -> {number:?}", line.number, line.text);
+            number.ok_or_else(|| line.error(format!("no calibration digits in `{}`", line.text)))
+        })
+        .sum()
 }
 
-fn get_calibration_number_legacy(s: &str) -> u64 {
-    let num_vec: Vec<u32> = s
-        .chars()
-        .filter(|char| char.is_numeric())
-        .map(|char| char.to_digit(10).unwrap())
-        .collect();
-    let first_number = num_vec.first().unwrap();
-    let last_number = num_vec.last().unwrap();
+pub fn get_calibration_number_legacy(s: &str) -> Option<u64> {
+    let num_vec: Vec<u32> = s.chars().filter_map(|char| char.to_digit(10)).collect();
+    let first_number = num_vec.first()?;
+    let last_number = num_vec.last()?;
 
-    format!("{first_number}{last_number}").parse().unwrap()
+    format!("{first_number}{last_number}").parse().ok()
 }
 
-fn get_calibration_number(s: &str) -> u64 {
+pub fn get_calibration_number(s: &str) -> Option<u64> {
     let word_nums = [
         ("zero", "0"),
         ("one", "1"),
@@ -91,19 +124,26 @@ fn get_calibration_number(s: &str) -> u64 {
         }
     }
 
-    let (first, last) = first_and_last_number.unwrap();
+    let (first, last) = first_and_last_number?;
     let (first, last) = (first.1.chars(), last.1.chars());
 
-    first
-        .chain(last)
-        .collect::<String>()
-        .parse::<u64>()
-        .unwrap_or(0)
+    first.chain(last).collect::<String>().parse::<u64>().ok()
 }
 
 #[cfg(test)]
 mod tests {
-    use crate::day01::{get_calibration_number, get_calibration_number_legacy};
+    use crate::{
+        day01::{get_calibration_number, get_calibration_number_legacy, Day01},
+        fixtures::examples,
+        params::Params,
+        solution::Solution,
+    };
+
+    examples! {
+        Day01;
+        part_one_works: One, "example1";
+        part_two_works: Two, "example2";
+    }
 
     #[test]
     fn produces_correct_calibration_numbers_legacy() {
@@ -144,8 +184,21 @@ zoneight234
         assert_eq!(sum, 281);
     }
 
-    fn get_test_values(s: &str, func: &dyn Fn(&str) -> u64) -> (Vec<u64>, u64) {
-        let num_array: Vec<u64> = s.lines().map(func).collect();
+    #[test]
+    fn lines_without_digits_are_errors() {
+        assert_eq!(get_calibration_number_legacy("abcone"), None);
+        assert_eq!(get_calibration_number("abcdef"), None);
+        assert_eq!(
+            Day01
+                .part_one("1abc2\nabc\n", &Params::default())
+                .unwrap_err()
+                .to_string(),
+            "line 2, column 1: no calibration digits in `abc`"
+        );
+    }
+
+    fn get_test_values(s: &str, func: &dyn Fn(&str) -> Option<u64>) -> (Vec<u64>, u64) {
+        let num_array: Vec<u64> = s.lines().map(|line| func(line).unwrap()).collect();
         let sum: u64 = num_array.iter().sum();
         (num_array, sum)
     }
@@ -1,44 +1,58 @@
-const INPUT: &str = include_str!("./input.txt");
+use crate::{
+    error::{AocError, Result},
+    params::{Param, Params},
+    solution::{Solution, Solver},
+    utils::{numbered_lines, Line},
+};
 
 #[derive(Default, Debug)]
-struct CubeSet {
-    red: u64,
-    green: u64,
-    blue: u64,
+pub struct CubeSet {
+    pub red: u64,
+    pub green: u64,
+    pub blue: u64,
 }
 
 impl CubeSet {
-    fn from_input(input: &str) -> impl Iterator<Item = impl Iterator<Item = CubeSet> + '_> + '_ {
-        input.lines().map(Self::from_game)
+    pub fn from_input(input: &str) -> Result<Vec<Vec<CubeSet>>> {
+        numbered_lines(input).map(Self::from_game).collect()
     }
 
-    fn from_game(s: &str) -> impl Iterator<Item = CubeSet> + '_ {
-        s.split_once(": ")
-            .unwrap()
+    pub fn from_game(line: Line) -> Result<Vec<CubeSet>> {
+        line.text
+            .split_once(": ")
+            .ok_or_else(|| line.error("expected `Game <id>: ` before the cube sets"))?
             .1
             .split("; ")
-            .map(CubeSet::from_set)
+            .map(|set_str| CubeSet::from_set(line, set_str))
+            .collect()
     }
 
-    fn from_set(set_str: &str) -> Self {
+    pub fn from_set(line: Line, set_str: &str) -> Result<Self> {
         let mut cube_set = Self::default();
         let split = set_str.split(", ");
 
         for s in split {
-            let (number, color): (&str, &str) = s.split_once(' ').unwrap();
-            let number: u64 = number.parse().unwrap();
-            match color {
-                "red" => cube_set.red += number,
-                "green" => cube_set.green += number,
-                "blue" => cube_set.blue += number,
-                _ => panic!(),
+            let (number, color): (&str, &str) = s
+                .split_once(' ')
+                .ok_or_else(|| line.error_at(s, "expected `<count> <color>`"))?;
+            let number: u64 = number
+                .parse()
+                .map_err(|_| line.error_at(number, format!("invalid cube count `{number}`")))?;
+            let count = match color {
+                "red" => &mut cube_set.red,
+                "green" => &mut cube_set.green,
+                "blue" => &mut cube_set.blue,
+                _ => return Err(line.error_at(color, format!("unknown cube color `{color}`"))),
             };
+            *count = count
+                .checked_add(number)
+                .ok_or_else(|| line.error_at(s, format!("too many {color} cubes")))?;
         }
 
-        cube_set
+        Ok(cube_set)
     }
 
-    fn get_minimum_cubes(game: impl Iterator<Item = CubeSet>) -> CubeSet {
+    pub fn get_minimum_cubes<'a>(game: impl Iterator<Item = &'a CubeSet>) -> CubeSet {
         let mut min_cube_set = Self::default();
         for cube_set in game {
             if cube_set.red > min_cube_set.red {
@@ -54,45 +68,89 @@ impl CubeSet {
         min_cube_set
     }
 
-    fn get_power(&self) -> u64 {
-        self.red * self.green * self.blue
+    pub fn get_power(&self) -> Result<u64> {
+        self.red
+            .checked_mul(self.green)
+            .and_then(|power| power.checked_mul(self.blue))
+            .ok_or_else(power_overflow)
     }
 
-    fn is_playable(&self, limit: &Self) -> bool {
+    pub fn is_playable(&self, limit: &Self) -> bool {
         self.red <= limit.red && self.green <= limit.green && self.blue <= limit.blue
     }
 }
 
-pub fn part_one() {
-    let limit = CubeSet {
-        red: 12,
-        green: 13,
-        blue: 14,
-    };
-
-    let games_iter = CubeSet::from_input(INPUT);
-    let id_sum = games_iter
-        .enumerate()
-        .map(|(key, game)| (key + 1, game))
-        .map(|(id, mut game)| (id, game.all(|cube_set| cube_set.is_playable(&limit))))
-        .filter(|(_, predicate)| *predicate)
-        .map(|(id, _)| id)
-        .sum::<usize>() as u64;
-    println!("{}", id_sum);
+fn power_overflow() -> AocError {
+    AocError::no_solution("the power of the cubes does not fit in 64 bits")
 }
 
-pub fn part_two() {
-    let games_iter = CubeSet::from_input(INPUT);
-    let power_sum: u64 = games_iter
-        .map(|game| CubeSet::get_minimum_cubes(game).get_power())
-        .sum();
+const PARAMS: &[Param] = &[
+    Param::new("red", "12"),
+    Param::new("green", "13"),
+    Param::new("blue", "14"),
+];
 
-    println!("{}", power_sum);
+pub struct Day02;
+
+impl Solution for Day02 {
+    fn day(&self) -> u8 {
+        2
+    }
+
+    fn title(&self) -> &'static str {
+        "Cube Conundrum"
+    }
+
+    fn params(&self) -> &'static [Param] {
+        PARAMS
+    }
+
+    fn parse_part_one<'a>(&self, input: &'a str, params: &Params) -> Result<Solver<'a>> {
+        let limit = CubeSet {
+            red: params.get_u64("red")?,
+            green: params.get_u64("green")?,
+            blue: params.get_u64("blue")?,
+        };
+        let games = CubeSet::from_input(input)?;
+
+        Ok(Box::new(move || {
+            let id_sum = games
+                .iter()
+                .enumerate()
+                .map(|(key, game)| (key + 1, game))
+                .map(|(id, game)| (id, game.iter().all(|cube_set| cube_set.is_playable(&limit))))
+                .filter(|(_, predicate)| *predicate)
+                .map(|(id, _)| id)
+                .sum::<usize>();
+            Ok(id_sum.into())
+        }))
+    }
+
+    fn parse_part_two<'a>(&self, input: &'a str, _params: &Params) -> Result<Option<Solver<'a>>> {
+        let games = CubeSet::from_input(input)?;
+
+        Ok(Some(Box::new(move || {
+            let power_sum = games.iter().try_fold(0u64, |sum, game| {
+                let power = CubeSet::get_minimum_cubes(game.iter()).get_power()?;
+                sum.checked_add(power).ok_or_else(power_overflow)
+            })?;
+            Ok(power_sum.into())
+        })))
+    }
 }
 
 #[cfg(test)]
 mod tests {
-    use super::CubeSet;
+    use crate::fixtures::examples;
+
+    use super::{CubeSet, Day02};
+
+    examples! {
+        Day02;
+        part_one_works: One, "example1";
+        part_two_works: Two, "example1";
+    }
+
     const EXAMPLE: &str = "Game 1: 3 blue, 4 red; 1 red, 2 green, 6 blue; 2 green
 Game 2: 1 blue, 2 green; 3 green, 4 blue, 1 red; 1 green, 1 blue
 Game 3: 8 green, 6 blue, 20 red; 5 blue, 4 red, 13 green; 5 green, 1 red
@@ -106,34 +164,26 @@ Game 5: 6 red, 1 blue, 3 green; 2 blue, 1 red, 2 green";
             green: 13,
             blue: 14,
         };
-        let games_iter = CubeSet::from_input(EXAMPLE);
-        let mut are_playable = games_iter
+        let games = CubeSet::from_input(EXAMPLE).unwrap();
+        let mut are_playable = games
+            .iter()
             .enumerate()
             .map(|(key, game)| (key + 1, game))
-            .map(|(id, mut game)| (id, game.all(|cube_set| cube_set.is_playable(&limit))));
+            .map(|(id, game)| (id, game.iter().all(|cube_set| cube_set.is_playable(&limit))));
 
         assert!(are_playable.next().unwrap().1);
         assert!(are_playable.next().unwrap().1);
         assert!(!are_playable.next().unwrap().1);
         assert!(!are_playable.next().unwrap().1);
         assert!(are_playable.next().unwrap().1);
-
-        let games_iter = CubeSet::from_input(EXAMPLE);
-        let sum = games_iter
-            .enumerate()
-            .map(|(key, game)| (key + 1, game))
-            .map(|(id, mut game)| (id, game.all(|cube_set| cube_set.is_playable(&limit))))
-            .filter(|(_, predicate)| *predicate)
-            .map(|(id, _)| id)
-            .sum::<usize>() as u64;
-        assert_eq!(sum, 8);
     }
 
     #[test]
     fn powers_of_min_cubes_are_correct() {
-        let games_iter = CubeSet::from_input(EXAMPLE);
-        let min_cubes_powers: Vec<u64> = games_iter
-            .map(|game| CubeSet::get_minimum_cubes(game).get_power())
+        let games = CubeSet::from_input(EXAMPLE).unwrap();
+        let min_cubes_powers: Vec<u64> = games
+            .iter()
+            .map(|game| CubeSet::get_minimum_cubes(game.iter()).get_power().unwrap())
             .collect();
 
         assert_eq!(min_cubes_powers[0], 48);
@@ -141,6 +191,17 @@ Game 5: 6 red, 1 blue, 3 green; 2 blue, 1 red, 2 green";
         assert_eq!(min_cubes_powers[2], 1560);
         assert_eq!(min_cubes_powers[3], 630);
         assert_eq!(min_cubes_powers[4], 36);
-        assert_eq!(min_cubes_powers.iter().sum::<u64>(), 2286);
+    }
+
+    #[test]
+    fn unknown_colors_are_errors() {
+        let err = CubeSet::from_input("Game 1: 3 blue\nGame 2: 1 purple, 2 red").unwrap_err();
+
+        assert_eq!(
+            err.to_string(),
+            "line 2, column 11: unknown cube color `purple`"
+        );
+        assert!(CubeSet::from_input("Game 1 3 blue").is_err());
+        assert!(CubeSet::from_input("Game 1: three blue").is_err());
     }
 }
@@ -1,21 +1,41 @@
+use crate::{
+    error::{AocError, Result},
+    params::Params,
+    solution::{Solution, Solver},
+};
+
 const POINT: u8 = b'.';
 const LINE_FEED: u8 = 10;
 const CARRIAGE_RETURN: u8 = 13;
 const ASTERISK: u8 = 42;
 
-const INPUT: &[u8] = include_bytes!("./input.txt");
+pub struct Day03;
 
-pub fn part_one() {
-    let sum = get_sum(INPUT);
-    println!("{}", sum);
-}
+impl Solution for Day03 {
+    fn day(&self) -> u8 {
+        3
+    }
+
+    fn title(&self) -> &'static str {
+        "Gear Ratios"
+    }
 
-pub fn part_two() {
-    let sum: u64 = get_gear_values(INPUT).iter().sum();
-    println!("{}", sum);
+    fn parse_part_one<'a>(&self, input: &'a str, _params: &Params) -> Result<Solver<'a>> {
+        Ok(Box::new(move || Ok(get_sum(input.as_bytes())?.into())))
+    }
+
+    fn parse_part_two<'a>(&self, input: &'a str, _params: &Params) -> Result<Option<Solver<'a>>> {
+        Ok(Some(Box::new(move || {
+            let sum = get_gear_values(input.as_bytes())?
+                .iter()
+                .try_fold(0u64, |sum, value| sum.checked_add(*value))
+                .ok_or_else(sum_overflow)?;
+            Ok(sum.into())
+        })))
+    }
 }
 
-pub fn get_sum(input: &[u8]) -> u64 {
+pub fn get_sum(input: &[u8]) -> Result<u64> {
     let lines: Vec<&[u8]> = input.split(|int| *int == LINE_FEED).collect();
     let mut sum: u64 = 0;
     let symbols_indexes = lines
@@ -52,9 +72,9 @@ pub fn get_sum(input: &[u8]) -> u64 {
                     {
                         if let Some(symbol_y) = symbols_indexes.get(y2) {
                             if symbol_y.contains(&x2) {
-                                let number_string: String =
-                                    number_vec.iter().map(|val| *val as char).collect();
-                                sum += number_string.parse().unwrap_or(0);
+                                let number = parse_part_number(&number_vec)
+                                    .map_err(|err| err.with_line(y + 1))?;
+                                sum = sum.checked_add(number).ok_or_else(sum_overflow)?;
                                 break 'sum;
                             }
                         }
@@ -64,10 +84,10 @@ pub fn get_sum(input: &[u8]) -> u64 {
             }
         }
     }
-    sum
+    Ok(sum)
 }
 
-pub fn get_gear_values(input: &[u8]) -> Vec<u64> {
+pub fn get_gear_values(input: &[u8]) -> Result<Vec<u64>> {
     let lines: Vec<&[u8]> = input.split(|int| *int == LINE_FEED).collect();
     let gear_indexes = lines
         .iter()
@@ -102,15 +122,18 @@ pub fn get_gear_values(input: &[u8]) -> Vec<u64> {
                     {
                         if let Some(gear_y) = gear_indexes.get(y2) {
                             if gear_y.contains(&x2) {
-                                let number_string: String =
-                                    number_vec.iter().map(|val| *val as char).collect();
-                                let number = number_string.parse::<u64>().unwrap();
+                                let number = parse_part_number(&number_vec)
+                                    .map_err(|err| err.with_line(y + 1))?;
 
                                 if let Some(gear) = gear_vec
                                     .iter_mut()
                                     .find(|(y, x, _, _)| *y == y2 && *x == x2)
                                 {
-                                    gear.2 *= number;
+                                    gear.2 = gear.2.checked_mul(number).ok_or_else(|| {
+                                        AocError::no_solution(
+                                            "the gear ratio does not fit in 64 bits",
+                                        )
+                                    })?;
                                     gear.3 = true;
                                 } else {
                                     gear_vec.push((y2, x2, number, false));
@@ -124,20 +147,35 @@ pub fn get_gear_values(input: &[u8]) -> Vec<u64> {
             number_vec.clear();
         }
     }
-    gear_vec
+    Ok(gear_vec
         .iter()
         .filter(|(_, _, _, is_gear)| *is_gear)
         .map(|(_, _, value, _)| *value)
-        .collect()
+        .collect())
 }
 
-// pub fn part_two() {
-//     todo!()
-// }
+fn sum_overflow() -> AocError {
+    AocError::no_solution("the sum does not fit in 64 bits")
+}
+
+pub fn parse_part_number(digits: &[u8]) -> Result<u64> {
+    let number_string: String = digits.iter().map(|val| *val as char).collect();
+    number_string
+        .parse()
+        .map_err(|_| AocError::parse(format!("part number `{number_string}` is too large")))
+}
 
 #[cfg(test)]
 mod tests {
-    use super::{get_gear_values, get_sum};
+    use crate::fixtures::examples;
+
+    use super::{get_gear_values, Day03};
+
+    examples! {
+        Day03;
+        part_one_works: One, "example1";
+        part_two_works: Two, "example1";
+    }
 
     const EXAMPLE: &[u8] = b"467..114..
 ...*......
@@ -151,16 +189,8 @@ mod tests {
 .664.598..";
 
     #[test]
-    fn it_works() {
-        let sum = get_sum(EXAMPLE);
-        assert_eq!(sum, 4361);
-    }
-
-    #[test]
-    fn part_two_works() {
-        let gear_values = get_gear_values(EXAMPLE);
-        assert_eq!(gear_values[0], 16345);
-        assert_eq!(gear_values[1], 451490);
-        assert_eq!(gear_values.iter().sum::<u64>(), 467835);
+    fn gear_values_are_correct() {
+        let gear_values = get_gear_values(EXAMPLE).unwrap();
+        assert_eq!(gear_values, [16345, 451490]);
     }
 }
@@ -1,56 +1,92 @@
-const INPUT: &str = include_str!("./input.txt");
+use crate::{
+    error::{AocError, Result},
+    params::Params,
+    solution::{Solution, Solver},
+    trace::trace,
+    utils::{numbered_lines, Line},
+};
 
-pub fn part_one() {
-    let cards = parse_cards(INPUT);
-    let won_numbers: Vec<Vec<u8>> = cards
-        .iter()
-        .map(|card| get_won_numbers(card.0.as_slice(), card.1.as_slice()))
-        .collect();
-    let total_points: u32 = won_numbers
-        .iter()
-        .map(|card| get_points(card.as_slice()))
-        .sum();
+pub type CardWithCount = (usize, u32, Vec<u8>, Vec<u8>);
+
+pub struct Day04;
+
+impl Solution for Day04 {
+    fn day(&self) -> u8 {
+        4
+    }
+
+    fn title(&self) -> &'static str {
+        "Scratchcards"
+    }
+
+    fn parse_part_one<'a>(&self, input: &'a str, _params: &Params) -> Result<Solver<'a>> {
+        let cards = parse_cards(input)?;
+
+        Ok(Box::new(move || {
+            let won_numbers: Vec<Vec<u8>> = cards
+                .iter()
+                .map(|card| get_won_numbers(card.0.as_slice(), card.1.as_slice()))
+                .collect();
+            let total_points = won_numbers.iter().try_fold(0u32, |sum, card| {
+                sum.checked_add(get_points(card.as_slice())?)
+                    .ok_or_else(points_overflow)
+            })?;
+
+            Ok(total_points.into())
+        }))
+    }
+
+    fn parse_part_two<'a>(&self, input: &'a str, _params: &Params) -> Result<Option<Solver<'a>>> {
+        let mut cards = parse_cards_with_id(input)?;
+
+        Ok(Some(Box::new(move || {
+            get_all_card_copies(&mut cards)?;
+            let sum = cards
+                .iter()
+                .try_fold(0u32, |sum, (_, count, _, _)| sum.checked_add(*count))
+                .ok_or_else(copies_overflow)?;
+
+            Ok(sum.into())
+        })))
+    }
+}
 
-    println!("3.1 answer: {}", total_points);
+pub fn parse_cards_iter(s: &str) -> impl Iterator<Item = Result<(Vec<u8>, Vec<u8>)>> + '_ {
+    numbered_lines(s).map(parse_card)
 }
 
-pub fn part_two() {
-    let mut cards = parse_cards_with_id(INPUT);
-    get_all_card_copies(&mut cards);
-    let sum = cards.iter().map(|(_,count,_,_)| count).sum::<u32>();
+pub fn parse_card(line: Line) -> Result<(Vec<u8>, Vec<u8>)> {
+    let card = line
+        .text
+        .split_once(": ")
+        .and_then(|(_, numbers)| numbers.split_once("| "))
+        .ok_or_else(|| line.error("expected `Card <id>: <numbers> | <numbers>`"))?;
 
-    println!("3.2 answer: {}", sum);
+    Ok((parse_numbers(line, card.0)?, parse_numbers(line, card.1)?))
 }
 
-fn parse_cards_iter(s: &str) -> impl Iterator<Item = (Vec<u8>, Vec<u8>)> + '_ {
-    s.lines()
-        .map(|line| line.split_once(": ").unwrap().1.split_once("| ").unwrap())
-        .map(|card| {
-            (
-                card.0
-                    .split_whitespace()
-                    .map(|number| number.parse::<u8>().unwrap())
-                    .collect::<Vec<u8>>(),
-                card.1
-                    .split_whitespace()
-                    .map(|number| number.parse::<u8>().unwrap())
-                    .collect::<Vec<u8>>(),
-            )
+pub fn parse_numbers(line: Line, s: &str) -> Result<Vec<u8>> {
+    s.split_whitespace()
+        .map(|number| {
+            number
+                .parse::<u8>()
+                .map_err(|_| line.error_at(number, format!("invalid card number `{number}`")))
         })
+        .collect()
 }
 
-fn parse_cards(s: &str) -> Vec<(Vec<u8>, Vec<u8>)> {
+pub fn parse_cards(s: &str) -> Result<Vec<(Vec<u8>, Vec<u8>)>> {
     parse_cards_iter(s).collect()
 }
 
-fn parse_cards_with_id(s: &str) -> Vec<(usize, u32, Vec<u8>, Vec<u8>)> {
+pub fn parse_cards_with_id(s: &str) -> Result<Vec<CardWithCount>> {
     parse_cards_iter(s)
         .enumerate()
-        .map(|(key, cards)| (key + 1, 1, cards.0, cards.1))
+        .map(|(key, cards)| cards.map(|cards| (key + 1, 1, cards.0, cards.1)))
         .collect()
 }
 
-fn get_won_numbers(winning_numbers: &[u8], numbers: &[u8]) -> Vec<u8> {
+pub fn get_won_numbers(winning_numbers: &[u8], numbers: &[u8]) -> Vec<u8> {
     numbers
         .iter()
         .filter(|number| winning_numbers.contains(number))
@@ -58,41 +94,68 @@ fn get_won_numbers(winning_numbers: &[u8], numbers: &[u8]) -> Vec<u8> {
         .collect::<Vec<u8>>()
 }
 
-fn get_all_card_copies(cards: &mut Vec<(usize, u32, Vec<u8>, Vec<u8>)>) {
+pub fn get_all_card_copies(cards: &mut [CardWithCount]) -> Result<()> {
     let mut i = 0;
     let mut len = cards.len();
 
     while i < len {
         let start_id = cards[i].0 + 1;
         let win_count = get_won_numbers(cards[i].2.as_slice(), cards[i].3.as_slice()).len();
-        let end_id = start_id + win_count;
+        let end_id = (start_id + win_count).min(len + 1);
+        trace!(
+            Debug,
+            "card {}: {win_count} winning numbers, {} copies",
+            cards[i].0,
+            cards[i].1
+        );
 
         if win_count != 0 {
             for j in start_id..end_id {
-                for _ in 0..cards[i].1 {
-                    cards[j - 1].1 += 1;
-                }
+                cards[j - 1].1 = cards[j - 1]
+                    .1
+                    .checked_add(cards[i].1)
+                    .ok_or_else(copies_overflow)?;
             }
             len = cards.len();
         }
         i += 1;
     }
+    Ok(())
+}
+
+fn copies_overflow() -> AocError {
+    AocError::no_solution("the number of card copies does not fit in 32 bits")
 }
 
-fn get_points(won_numbers: &[u8]) -> u32 {
+fn points_overflow() -> AocError {
+    AocError::no_solution("the number of points does not fit in 32 bits")
+}
+
+pub fn get_points(won_numbers: &[u8]) -> Result<u32> {
     if won_numbers.is_empty() {
-        0
+        Ok(0)
     } else {
-        u32::pow(2, (won_numbers.len() as u32) - 1)
+        u32::try_from(won_numbers.len() - 1)
+            .ok()
+            .and_then(|exponent| 2u32.checked_pow(exponent))
+            .ok_or_else(points_overflow)
     }
 }
 
 #[cfg(test)]
 mod tests {
+    use crate::fixtures::examples;
+
     use super::{
-        get_all_card_copies, get_points, get_won_numbers, parse_cards, parse_cards_with_id,
+        get_all_card_copies, get_points, get_won_numbers, parse_cards, parse_cards_with_id, Day04,
     };
 
+    examples! {
+        Day04;
+        part_one_works: One, "example1";
+        part_two_works: Two, "example1";
+    }
+
     const EXAMPLE: &str = "Card 1: 41 48 83 86 17 | 83 86  6 31 17  9 48 53
 Card 2: 13 32 20 16 61 | 61 30 68 82 17 32 24 19
 Card 3:  1 21 53 59 44 | 69 82 63 72 16 21 14  1
@@ -101,15 +164,15 @@ Card 5: 87 83 26 28 32 | 88 30 70 12 93 22 82 36
 Card 6: 31 18 13 56 72 | 74 77 10 23 35 67 36 11";
 
     #[test]
-    fn part_one_works() {
-        let cards = parse_cards(EXAMPLE);
+    fn won_numbers_are_scored() {
+        let cards = parse_cards(EXAMPLE).unwrap();
         let won_numbers: Vec<Vec<u8>> = cards
             .iter()
             .map(|card| get_won_numbers(card.0.as_slice(), card.1.as_slice()))
             .collect();
         let game_points: Vec<u32> = won_numbers
             .iter()
-            .map(|card| get_points(card.as_slice()))
+            .map(|card| get_points(card.as_slice()).unwrap())
             .collect();
 
         assert!(won_numbers[0]
@@ -121,21 +184,35 @@ Card 6: 31 18 13 56 72 | 74 77 10 23 35 67 36 11";
         assert!(won_numbers[4].is_empty());
         assert!(won_numbers[5].is_empty());
         assert_eq!(game_points.as_slice(), &[8, 2, 2, 1, 0, 0]);
-        assert_eq!(game_points.iter().sum::<u32>(), 13);
+        assert_eq!(get_points(&[1; 32]).unwrap(), 1 << 31);
+        assert!(get_points(&[1; 33]).is_err());
     }
 
     #[test]
-    fn part_two_works() {
-        let mut cards = parse_cards_with_id(EXAMPLE);
-        get_all_card_copies(&mut cards);
-        let sum = cards.iter().map(|(_,count,_,_)| count).sum::<u32>();
-        
+    fn card_copies_are_counted() {
+        let mut cards = parse_cards_with_id(EXAMPLE).unwrap();
+        get_all_card_copies(&mut cards).unwrap();
+
         assert_eq!(cards[0].1, 1);
         assert_eq!(cards[1].1, 2);
         assert_eq!(cards[2].1, 4);
         assert_eq!(cards[3].1, 8);
         assert_eq!(cards[4].1, 14);
         assert_eq!(cards[5].1, 1);
-        assert_eq!(sum, 30);
+
+        let mut cards = parse_cards_with_id("Card 1: 1 2 | 1 2\nCard 2: 3 | 3").unwrap();
+        get_all_card_copies(&mut cards).unwrap();
+        assert_eq!(cards[1].1, 2);
+    }
+
+    #[test]
+    fn invalid_numbers_are_errors() {
+        let err = parse_cards("Card 1: 41 48 | 83 86\nCard 2: 13 x2 | 61 30").unwrap_err();
+
+        assert_eq!(
+            err.to_string(),
+            "line 2, column 12: invalid card number `x2`"
+        );
+        assert!(parse_cards("Card 1: 41 48 83 86").is_err());
     }
 }
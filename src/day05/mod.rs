@@ -1,96 +1,156 @@
-const INPUT: &str = include_str!("./input.txt");
+use crate::{
+    error::{AocError, Result},
+    params::Params,
+    solution::{Solution, Solver},
+    trace::trace,
+    utils::{numbered_lines, Line},
+};
 
 #[derive(Debug)]
-struct ConversionNumbers {
-    dest_num: u64,
-    src_num: u64,
-    add: u64,
+pub struct ConversionNumbers {
+    pub dest_num: u64,
+    pub src_num: u64,
+    pub add: u64,
 }
 
-fn convert(number: u64, conversion_map: &[ConversionNumbers]) -> u64 {
+pub fn convert(number: u64, conversion_map: &[ConversionNumbers]) -> Result<u64> {
     for numbers in conversion_map {
-        if (numbers.src_num..=numbers.src_num + numbers.add).contains(&number) {
-            return number + numbers.dest_num - numbers.src_num;
+        if number >= numbers.src_num && number - numbers.src_num <= numbers.add {
+            return (number - numbers.src_num)
+                .checked_add(numbers.dest_num)
+                .ok_or_else(|| {
+                    AocError::no_solution(format!(
+                        "converting {number} with `{} {} {}` does not fit in 64 bits",
+                        numbers.dest_num, numbers.src_num, numbers.add
+                    ))
+                });
         }
     }
-    number
+    Ok(number)
 }
 
-pub fn part_one() {
-    let conversions = from_input(INPUT);
-    let minimum = find_lowest(conversions.as_slice());
-    println!("5.1 answer: {}", minimum);
+pub struct Day05;
+
+impl Solution for Day05 {
+    fn day(&self) -> u8 {
+        5
+    }
+
+    fn title(&self) -> &'static str {
+        "If You Give A Seed A Fertilizer"
+    }
+
+    fn parse_part_one<'a>(&self, input: &'a str, _params: &Params) -> Result<Solver<'a>> {
+        let conversions = from_input(input)?;
+        Ok(Box::new(move || {
+            Ok(find_lowest(conversions.as_slice()).into())
+        }))
+    }
 }
 
-fn parse_seeds(input: &str) -> Vec<u64> {
-    input
-        .lines()
+pub fn parse_seeds(input: &str) -> Result<Vec<u64>> {
+    let line = numbered_lines(input)
         .next()
-        .unwrap()
+        .ok_or_else(|| AocError::parse("expected `seeds: ` on the first line").with_line(1))?;
+
+    line.text
         .split_once("seeds: ")
-        .unwrap()
+        .ok_or_else(|| line.error("expected `seeds: ` on the first line"))?
         .1
         .split_ascii_whitespace()
-        .map(|num_str| num_str.parse().unwrap())
+        .map(|num_str| parse_number(line, num_str))
         .collect()
 }
 
-fn from_input(input: &str) -> Vec<[u64; 8]> {
-    let seeds = parse_seeds(input);
-    let conversion_maps = get_conversion_maps(input);
+pub fn parse_number(line: Line, num_str: &str) -> Result<u64> {
+    num_str
+        .parse()
+        .map_err(|_| line.error_at(num_str, format!("invalid number `{num_str}`")))
+}
+
+pub fn from_input(input: &str) -> Result<Vec<[u64; 8]>> {
+    let seeds = parse_seeds(input)?;
+    let conversion_maps = get_conversion_maps(input)?;
     get_conversions(conversion_maps.as_slice(), seeds.as_slice())
 }
 
-fn get_conversion_maps(input: &str) -> Vec<Vec<ConversionNumbers>> {
-    let mut lines = input.lines();
+pub fn get_conversion_maps(input: &str) -> Result<Vec<Vec<ConversionNumbers>>> {
+    let mut lines = numbered_lines(input);
     let mut conversion_maps: Vec<Vec<ConversionNumbers>> = Vec::with_capacity(7);
     let mut current_conversion_numbers: Option<Vec<ConversionNumbers>> = None;
 
     lines.nth(1);
     for line in lines {
-        if line.contains("map:") {
+        if line.text.contains("map:") {
             current_conversion_numbers = Some(vec![]);
             continue;
         }
 
-        if line.is_empty() {
-            conversion_maps.push(current_conversion_numbers.unwrap());
-            current_conversion_numbers = None;
+        if line.text.is_empty() {
+            let conversion_numbers = current_conversion_numbers
+                .take()
+                .ok_or_else(|| line.error("unexpected empty line"))?;
+            conversion_maps.push(conversion_numbers);
             continue;
         }
 
-        if let Some(ref mut cm) = current_conversion_numbers {
-            let literals: Vec<u64> = line
-                .trim_end()
-                .splitn(3, ' ')
-                .map(|num_str| num_str.parse::<u64>().unwrap())
-                .collect();
-            cm.push(ConversionNumbers {
-                dest_num: literals[0],
-                src_num: literals[1],
-                add: literals[2],
-            })
-        }
+        let Some(ref mut cm) = current_conversion_numbers else {
+            return Err(line.error("expected a `<name> map:` header"));
+        };
+        let literals = line
+            .text
+            .trim_end()
+            .splitn(3, ' ')
+            .map(|num_str| parse_number(line, num_str))
+            .collect::<Result<Vec<u64>>>()?;
+        let [dest_num, src_num, add] = literals[..] else {
+            return Err(line.error("expected `<destination> <source> <length>`"));
+        };
+        cm.push(ConversionNumbers {
+            dest_num,
+            src_num,
+            add,
+        })
+    }
+    if let Some(conversion_numbers) = current_conversion_numbers.take() {
+        conversion_maps.push(conversion_numbers);
+    }
+    if conversion_maps.len() != 7 {
+        return Err(AocError::parse(format!(
+            "expected 7 conversion maps but found {}",
+            conversion_maps.len()
+        )));
     }
-    conversion_maps.push(current_conversion_numbers.take().unwrap());
 
-    conversion_maps
+    Ok(conversion_maps)
 }
 
-fn get_conversions(conversion_maps: &[Vec<ConversionNumbers>], seeds: &[u64]) -> Vec<[u64; 8]> {
+pub fn get_conversions(
+    conversion_maps: &[Vec<ConversionNumbers>],
+    seeds: &[u64],
+) -> Result<Vec<[u64; 8]>> {
     let mut conversions: Vec<[u64; 8]> = Vec::with_capacity(seeds.len());
 
     for seed in seeds {
         let mut conversion_set = [*seed, 0, 0, 0, 0, 0, 0, 0];
         for i in 0..conversion_maps.len() {
-            conversion_set[i + 1] = convert(conversion_set[i], &conversion_maps[i]);
+            conversion_set[i + 1] = convert(conversion_set[i], &conversion_maps[i])?;
         }
+        trace!(
+            Debug,
+            "seed {}",
+            conversion_set[..=conversion_maps.len()]
+                .iter()
+                .map(u64::to_string)
+                .collect::<Vec<_>>()
+                .join(" -> ")
+        );
         conversions.push(conversion_set);
     }
-    conversions
+    Ok(conversions)
 }
 
-fn find_lowest(conversions: &[[u64; 8]]) -> u64 {
+pub fn find_lowest(conversions: &[[u64; 8]]) -> u64 {
     conversions.iter().map(|set| set[7]).min().unwrap_or(0)
 }
 
@@ -103,7 +163,15 @@ mod tests {
     // number (the destination). This lets the gardener and his team know which soil to use with
     // which seeds, which water to use with which fertilizer, and so on.
 
-    use crate::day05::{find_lowest, from_input};
+    use crate::{
+        day05::{from_input, Day05},
+        fixtures::examples,
+    };
+
+    examples! {
+        Day05;
+        part_one_works: One, "example1";
+    }
 
     const EXAMPLE: &str = "seeds: 79 14 55 13
 
@@ -140,14 +208,32 @@ humidity-to-location map:
 56 93 4";
 
     #[test]
-    fn part_one_works() {
-        let conversions = from_input(EXAMPLE);
-        let minimum = find_lowest(conversions.as_slice());
+    fn seeds_are_converted() {
+        let conversions = from_input(EXAMPLE).unwrap();
 
         assert_eq!(conversions[0], [79, 81, 81, 81, 74, 78, 78, 82]);
         assert_eq!(conversions[1], [14, 14, 53, 49, 42, 42, 43, 43]);
         assert_eq!(conversions[2], [55, 57, 57, 53, 46, 82, 82, 86]);
         assert_eq!(conversions[3], [13, 13, 52, 41, 34, 34, 35, 35]);
-        assert_eq!(minimum, 35);
+    }
+
+    #[test]
+    fn malformed_almanacs_are_errors() {
+        let err = from_input("seeds: 79 1x4 55").unwrap_err();
+        assert_eq!(
+            format!("{err:#}"),
+            "line 1, column 11: invalid number `1x4`
+  |
+1 | seeds: 79 1x4 55
+  |           ^^^"
+        );
+
+        let almanac = EXAMPLE.replace("52 50 48", "52 50");
+        let err = from_input(&almanac).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "line 5, column 1: expected `<destination> <source> <length>`"
+        );
+        assert!(from_input(&EXAMPLE.replace("\n\nhumidity", "\nhumidity")).is_err());
     }
 }
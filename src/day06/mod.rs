@@ -1,29 +1,35 @@
-const INPUT: &str = include_str!("./input.txt");
+use crate::{
+    error::{AocError, Result},
+    params::Params,
+    solution::{Solution, Solver},
+    utils::{numbered_lines, Line},
+};
 
-type MarginOfError = (u64, u64);
-type Millisecond = u64;
-type Distance = u64;
-
-enum ButtonState {
-    Held,
-    Released,
-}
+pub type MarginOfError = (u64, u64);
+pub type Millisecond = u64;
+pub type Distance = u64;
 
 #[derive(Debug)]
-struct Race {
-    time: Millisecond,
-    record: Distance,
+pub struct Race {
+    pub time: Millisecond,
+    pub record: Distance,
 }
 
 impl Race {
-    fn get_margin_of_error(&self) -> MarginOfError {
+    pub fn get_margin_of_error(&self) -> Result<MarginOfError> {
         let (time, record) = (self.time, self.record);
 
+        if get_distance(time, time / 2) <= record {
+            return Err(AocError::no_solution(format!(
+                "a race of {time} ms cannot beat the record of {record} mm"
+            )));
+        }
+
         let mut left = 0;
         let mut right = time;
-        let mut mid = time / 2;
-        let mut last_record_beating_index = mid;
+        let mut last_record_beating_index = time / 2;
         while left <= right {
+            let mid = left + (right - left) / 2;
             let distance = get_distance(time, mid);
 
             if distance > record {
@@ -32,15 +38,14 @@ impl Race {
             } else {
                 left = mid + 1;
             }
-            mid = (left + right) / 2;
         }
         let left_bound = last_record_beating_index;
 
         let mut left = 0;
         let mut right = time;
-        let mut mid = time / 2;
-        let mut last_record_beating_index = mid;
+        let mut last_record_beating_index = time / 2;
         while left <= right {
+            let mid = left + (right - left) / 2;
             let distance = get_distance(time, mid);
 
             if distance > record {
@@ -49,154 +54,200 @@ impl Race {
             } else {
                 right = mid - 1;
             }
-            mid = (left + right) / 2;
         }
         let right_bound = last_record_beating_index;
 
-        (left_bound, right_bound)
-
-        // let mut left_bound = self.time;
-        // let mut right_bound = 0;
-
-        // [1..self.time].binary_search(||)
-
-        // for i in 1..self.time {
-        //     let distance = get_distance(self.time, i);
-        //     if distance > self.record {
-        //         left_bound = i;
-        //         break;
-        //     }
-        // }
-        // for i in (1..self.time).rev() {
-        //     let distance = get_distance(self.time, i);
-        //     if distance > self.record {
-        //         right_bound = i;
-        //         break;
-        //     }
-        // }
-        // (left_bound, right_bound)
+        Ok((left_bound, right_bound))
+    }
+
+    pub fn get_margin_of_error_linear(&self) -> Result<MarginOfError> {
+        let beats_record = |i: &u64| get_distance(self.time, *i) > self.record;
+        let left_bound = (0..=self.time).find(beats_record);
+        let right_bound = (0..=self.time).rev().find(beats_record);
+
+        match (left_bound, right_bound) {
+            (Some(left_bound), Some(right_bound)) => Ok((left_bound, right_bound)),
+            _ => Err(AocError::no_solution(format!(
+                "a race of {} ms cannot beat the record of {} mm",
+                self.time, self.record
+            ))),
+        }
     }
 }
 
-pub fn part_one() {
-    let races = parse_races(INPUT);
-    let margins_of_error: Vec<MarginOfError> = races
-        .iter()
-        .map(|race| race.get_margin_of_error())
-        .collect();
-    let product = get_error_product(margins_of_error.as_slice());
+pub struct Day06;
 
-    println!("6.1 answer: {}", product);
-}
+impl Solution for Day06 {
+    fn day(&self) -> u8 {
+        6
+    }
 
-pub fn part_two() {
-    let race = parse_long_race(INPUT);
-    let margin_of_error = race.get_margin_of_error();
+    fn title(&self) -> &'static str {
+        "Wait For It"
+    }
+
+    fn parse_part_one<'a>(&self, input: &'a str, _params: &Params) -> Result<Solver<'a>> {
+        let races = parse_races(input)?;
+
+        Ok(Box::new(move || {
+            let margins_of_error: Vec<MarginOfError> = races
+                .iter()
+                .map(|race| race.get_margin_of_error())
+                .collect::<Result<_>>()?;
+
+            Ok(get_error_product(margins_of_error.as_slice())?.into())
+        }))
+    }
 
-    println!("6.2 answer: {}", 1 + margin_of_error.1 - margin_of_error.0);
+    fn parse_part_two<'a>(&self, input: &'a str, _params: &Params) -> Result<Option<Solver<'a>>> {
+        let race = parse_long_race(input)?;
+
+        Ok(Some(Box::new(move || {
+            let margin_of_error = race.get_margin_of_error()?;
+            Ok((1 + margin_of_error.1 - margin_of_error.0).into())
+        })))
+    }
 }
 
-fn parse_races(input: &str) -> Vec<Race> {
-    let mut lines = input.lines();
-    let times = parse_number(lines.next().unwrap());
-    let records = parse_number(lines.next().unwrap());
+pub fn parse_races(input: &str) -> Result<Vec<Race>> {
+    let (time_line, record_line) = get_sheet_lines(input)?;
+    let times = parse_number(time_line)?;
+    let records = parse_number(record_line)?;
     let len = times.len();
     let mut race_vec = Vec::with_capacity(len);
 
+    if records.len() != len {
+        return Err(record_line.error(format!("found {len} times but {} distances", records.len())));
+    }
     for i in 0..len {
         race_vec.push(Race {
             time: times[i],
             record: records[i],
         })
     }
-    race_vec
+    Ok(race_vec)
+}
+
+pub fn get_sheet_lines(input: &str) -> Result<(Line<'_>, Line<'_>)> {
+    let mut lines = numbered_lines(input);
+    let time_line = lines
+        .next()
+        .ok_or_else(|| AocError::parse("missing `Time:` line").with_line(1))?;
+    let record_line = lines
+        .next()
+        .ok_or_else(|| AocError::parse("missing `Distance:` line").with_line(2))?;
+
+    Ok((time_line, record_line))
 }
 
-fn parse_number(line: &str) -> Vec<u64> {
-    line.split_ascii_whitespace()
+pub fn parse_number(line: Line) -> Result<Vec<u64>> {
+    line.text
+        .split_ascii_whitespace()
         .enumerate()
         .filter(|(key, _)| key != &0)
-        .map(|(_, num_str)| num_str.parse().unwrap())
+        .map(|(_, num_str)| {
+            num_str
+                .parse()
+                .map_err(|_| line.error_at(num_str, format!("invalid number `{num_str}`")))
+        })
         .collect()
 }
 
-fn parse_long_number(line: &str) -> u64 {
+pub fn parse_long_number(line: Line) -> Result<u64> {
     let mut number_string = String::new();
 
-    line.split_ascii_whitespace()
+    for (_, num_str) in line
+        .text
+        .split_ascii_whitespace()
         .enumerate()
         .filter(|(key, _)| key != &0)
-        .for_each(|(_, num_str)| number_string.push_str(num_str));
-    number_string.parse().unwrap()
+    {
+        if !num_str.bytes().all(|byte| byte.is_ascii_digit()) {
+            return Err(line.error_at(num_str, format!("invalid number `{num_str}`")));
+        }
+        number_string.push_str(num_str);
+    }
+    number_string
+        .parse()
+        .map_err(|_| line.error(format!("invalid number `{number_string}`")))
 }
 
-fn parse_long_race(input: &str) -> Race {
-    let mut lines = input.lines();
-    let time = parse_long_number(lines.next().unwrap());
-    let record = parse_long_number(lines.next().unwrap());
+pub fn parse_long_race(input: &str) -> Result<Race> {
+    let (time_line, record_line) = get_sheet_lines(input)?;
+    let time = parse_long_number(time_line)?;
+    let record = parse_long_number(record_line)?;
 
-    Race { time, record }
+    Ok(Race { time, record })
 }
 
-fn get_distance(race_time: Millisecond, hold_time: Millisecond) -> Distance {
-    let mut accel = 0u64;
-    let mut distance = 0u64;
-    let mut button_state;
-
-    for i in 1..=race_time {
-        if i > hold_time {
-            button_state = ButtonState::Released;
-        } else {
-            button_state = ButtonState::Held;
-        }
-
-        match button_state {
-            ButtonState::Held => {
-                accel += 1;
-            }
-            ButtonState::Released => {
-                distance += accel;
-            }
-        }
-    }
-    distance
+pub fn get_distance(race_time: Millisecond, hold_time: Millisecond) -> Distance {
+    let hold_time = hold_time.min(race_time);
+    hold_time.saturating_mul(race_time - hold_time)
 }
 
-fn get_error_product(margins: &[MarginOfError]) -> u64 {
+pub fn get_error_product(margins: &[MarginOfError]) -> Result<u64> {
     margins
         .iter()
-        .map(|(left, right)| 1 + right - left)
-        .product()
+        .try_fold(1u64, |product, (left, right)| {
+            product.checked_mul(1 + right - left)
+        })
+        .ok_or_else(|| AocError::no_solution("the product of the margins does not fit in 64 bits"))
 }
 
 #[cfg(test)]
 mod tests {
-    use super::{get_error_product, parse_long_race, parse_races, MarginOfError};
+    use crate::fixtures::examples;
+
+    use super::{get_distance, parse_long_race, parse_races, Day06, MarginOfError};
+
+    examples! {
+        Day06;
+        part_one_works: One, "example1";
+        part_two_works: Two, "example1";
+    }
 
     const EXAMPLE: &str = "Time:      7  15   30
 Distance:  9  40  200";
 
     #[test]
-    fn part_one_works() {
-        let races = parse_races(EXAMPLE);
+    fn margins_of_error_are_correct() {
+        let races = parse_races(EXAMPLE).unwrap();
         let margins_of_error: Vec<MarginOfError> = races
             .iter()
-            .map(|race| race.get_margin_of_error())
+            .map(|race| race.get_margin_of_error().unwrap())
             .collect();
-        let product = get_error_product(margins_of_error.as_slice());
 
         assert_eq!(margins_of_error[0], (2, 5));
         assert_eq!(margins_of_error[1], (4, 11));
         assert_eq!(margins_of_error[2], (11, 19));
-        assert_eq!(product, 288);
+        assert_eq!(
+            parse_long_race(EXAMPLE)
+                .unwrap()
+                .get_margin_of_error()
+                .unwrap(),
+            (14, 71516)
+        );
+    }
+
+    #[test]
+    fn malformed_sheets_are_errors() {
+        let err = parse_races("Time:      7  15   30\nDistance:  9  40").unwrap_err();
+
+        assert_eq!(
+            err.to_string(),
+            "line 2, column 1: found 3 times but 2 distances"
+        );
+        assert!(parse_races("Time:      7  15   30").is_err());
+        assert!(parse_long_race("Time:      7  1x5\nDistance:  9  40").is_err());
     }
 
     #[test]
-    fn part_two_works() {
-        let race = parse_long_race(EXAMPLE);
-        let margin_of_error = race.get_margin_of_error();
+    fn unbeatable_records_are_errors() {
+        let races = parse_races("Time: 0 4 30\nDistance: 0 4 200").unwrap();
 
-        assert_eq!(margin_of_error, (14, 71516));
-        assert_eq!(1 + margin_of_error.1 - margin_of_error.0, 71503);
+        assert!(races[0].get_margin_of_error().is_err());
+        assert!(races[1].get_margin_of_error().is_err());
+        assert_eq!(races[2].get_margin_of_error().unwrap(), (11, 19));
+        assert_eq!(get_distance(u64::MAX, u64::MAX / 2), u64::MAX);
     }
 }
@@ -1,15 +1,21 @@
-const INPUT: &str = include_str!("./input.txt");
+use std::{collections::HashMap, fmt::Debug, str::FromStr};
 
-use std::{collections::HashMap, str::FromStr};
+use crate::{
+    error::{AocError, Result},
+    params::Params,
+    solution::{Solution, Solver},
+    trace::trace,
+    utils::numbered_lines,
+};
 
-trait CamelCard: Clone + Copy + PartialEq + Eq + PartialOrd + Ord + Sized {
-    fn from_char(c: char) -> Result<Self, ()>;
+pub trait CamelCard: Clone + Copy + Debug + PartialEq + Eq + PartialOrd + Ord + Sized {
+    fn from_char(c: char) -> Option<Self>;
     fn get_hand_type(cards: &[Self; 5]) -> HandType;
 }
 
 #[derive(Clone, Copy, Debug, Hash, PartialEq, Eq, PartialOrd, Ord)]
 #[repr(u8)]
-enum CardLegacy {
+pub enum CardLegacy {
     Two = 2,
     Three = 3,
     Four = 4,
@@ -26,22 +32,22 @@ enum CardLegacy {
 }
 
 impl CamelCard for CardLegacy {
-    fn from_char(c: char) -> Result<Self, ()> {
+    fn from_char(c: char) -> Option<Self> {
         match c {
-            'A' => Ok(Self::Ace),
-            'K' => Ok(Self::King),
-            'Q' => Ok(Self::Queen),
-            'J' => Ok(Self::Jack),
-            'T' => Ok(Self::Ten),
-            '9' => Ok(Self::Nine),
-            '8' => Ok(Self::Eight),
-            '7' => Ok(Self::Seven),
-            '6' => Ok(Self::Six),
-            '5' => Ok(Self::Five),
-            '4' => Ok(Self::Four),
-            '3' => Ok(Self::Three),
-            '2' => Ok(Self::Two),
-            _ => Err(()),
+            'A' => Some(Self::Ace),
+            'K' => Some(Self::King),
+            'Q' => Some(Self::Queen),
+            'J' => Some(Self::Jack),
+            'T' => Some(Self::Ten),
+            '9' => Some(Self::Nine),
+            '8' => Some(Self::Eight),
+            '7' => Some(Self::Seven),
+            '6' => Some(Self::Six),
+            '5' => Some(Self::Five),
+            '4' => Some(Self::Four),
+            '3' => Some(Self::Three),
+            '2' => Some(Self::Two),
+            _ => None,
         }
     }
 
@@ -76,7 +82,7 @@ impl CamelCard for CardLegacy {
 
 #[derive(Clone, Copy, Debug, Hash, PartialEq, Eq, PartialOrd, Ord)]
 #[repr(u8)]
-enum Card {
+pub enum Card {
     Jack = 1,
     Two = 2,
     Three = 3,
@@ -93,22 +99,22 @@ enum Card {
 }
 
 impl CamelCard for Card {
-    fn from_char(c: char) -> Result<Self, ()> {
+    fn from_char(c: char) -> Option<Self> {
         match c {
-            'A' => Ok(Self::Ace),
-            'K' => Ok(Self::King),
-            'Q' => Ok(Self::Queen),
-            'J' => Ok(Self::Jack),
-            'T' => Ok(Self::Ten),
-            '9' => Ok(Self::Nine),
-            '8' => Ok(Self::Eight),
-            '7' => Ok(Self::Seven),
-            '6' => Ok(Self::Six),
-            '5' => Ok(Self::Five),
-            '4' => Ok(Self::Four),
-            '3' => Ok(Self::Three),
-            '2' => Ok(Self::Two),
-            _ => Err(()),
+            'A' => Some(Self::Ace),
+            'K' => Some(Self::King),
+            'Q' => Some(Self::Queen),
+            'J' => Some(Self::Jack),
+            'T' => Some(Self::Ten),
+            '9' => Some(Self::Nine),
+            '8' => Some(Self::Eight),
+            '7' => Some(Self::Seven),
+            '6' => Some(Self::Six),
+            '5' => Some(Self::Five),
+            '4' => Some(Self::Four),
+            '3' => Some(Self::Three),
+            '2' => Some(Self::Two),
+            _ => None,
         }
     }
 
@@ -150,7 +156,7 @@ impl CamelCard for Card {
 
 #[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
 #[repr(u8)]
-enum HandType {
+pub enum HandType {
     HighCard = 1,
     OnePair = 2,
     TwoPair = 4,
@@ -160,11 +166,11 @@ enum HandType {
     FiveOfAKind = 64,
 }
 
-#[derive(Eq)]
-struct Hand<T: CamelCard> {
-    bid: u64,
-    cards: [T; 5],
-    hand_type: HandType,
+#[derive(Debug, Eq)]
+pub struct Hand<T: CamelCard> {
+    pub bid: u64,
+    pub cards: [T; 5],
+    pub hand_type: HandType,
 }
 
 impl<T: CamelCard> PartialEq for Hand<T> {
@@ -175,43 +181,64 @@ impl<T: CamelCard> PartialEq for Hand<T> {
 
 impl<T: CamelCard> PartialOrd for Hand<T> {
     fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<T: CamelCard> Ord for Hand<T> {
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
         match self.hand_type.cmp(&other.hand_type) {
-            std::cmp::Ordering::Less => return Some(std::cmp::Ordering::Less),
+            std::cmp::Ordering::Less => return std::cmp::Ordering::Less,
             std::cmp::Ordering::Equal => (),
-            std::cmp::Ordering::Greater => return Some(std::cmp::Ordering::Greater),
+            std::cmp::Ordering::Greater => return std::cmp::Ordering::Greater,
         };
         for i in 0..5 {
             // SAFETY: length of Hand::cards is always 5
             match self.cards[i].cmp(&other.cards[i]) {
-                std::cmp::Ordering::Less => return Some(std::cmp::Ordering::Less),
+                std::cmp::Ordering::Less => return std::cmp::Ordering::Less,
                 std::cmp::Ordering::Equal => (),
-                std::cmp::Ordering::Greater => return Some(std::cmp::Ordering::Greater),
+                std::cmp::Ordering::Greater => return std::cmp::Ordering::Greater,
             }
         }
-        Some(std::cmp::Ordering::Equal)
-    }
-}
-
-impl<T: CamelCard> Ord for Hand<T> {
-    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
-        self.partial_cmp(other).unwrap()
+        std::cmp::Ordering::Equal
     }
 }
 
 impl<T: CamelCard> FromStr for Hand<T> {
-    type Err = ();
+    type Err = AocError;
 
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
+    fn from_str(s: &str) -> Result<Self> {
         let mut cards = Vec::with_capacity(5);
 
-        let (cards_str, bid_str) = s.split_once(' ').ok_or(())?;
+        let (cards_str, bid_str) = s
+            .split_once(' ')
+            .ok_or_else(|| AocError::parse("expected `<cards> <bid>`").with_span(0, s.len(), s))?;
 
-        cards_str
-            .chars()
-            .for_each(|char| cards.push(T::from_char(char).unwrap()));
-        let cards: [T; 5] = cards[0..5].try_into().unwrap();
+        for (column, char) in cards_str.char_indices() {
+            let card = T::from_char(char).ok_or_else(|| {
+                AocError::parse(format!("invalid card `{char}`")).with_span(
+                    column,
+                    char.len_utf8(),
+                    s,
+                )
+            })?;
+            cards.push(card);
+        }
+        let cards: [T; 5] = cards.try_into().map_err(|cards: Vec<T>| {
+            AocError::parse(format!("expected 5 cards but found {}", cards.len())).with_span(
+                0,
+                cards_str.len(),
+                s,
+            )
+        })?;
         let hand_type = T::get_hand_type(&cards);
-        let bid = bid_str.parse().map_err(|_| ())?;
+        let bid = bid_str.parse().map_err(|_| {
+            AocError::parse(format!("invalid bid `{bid_str}`")).with_span(
+                cards_str.len() + 1,
+                bid_str.len(),
+                s,
+            )
+        })?;
 
         Ok(Self {
             bid,
@@ -221,41 +248,82 @@ impl<T: CamelCard> FromStr for Hand<T> {
     }
 }
 
-pub fn part_one() {
-    let mut hands: Vec<Hand<CardLegacy>> = INPUT
-        .lines()
-        .map(|line| Hand::from_str(line).unwrap())
-        .collect();
-    hands.sort();
-    let sum: u64 = hands
-        .iter()
-        .enumerate()
-        .map(|(k, v)| (k + 1) as u64 * v.bid)
-        .sum();
-
-    println!("7.1 answer: {}", sum);
+pub struct Day07;
+
+impl Solution for Day07 {
+    fn day(&self) -> u8 {
+        7
+    }
+
+    fn title(&self) -> &'static str {
+        "Camel Cards"
+    }
+
+    fn parse_part_one<'a>(&self, input: &'a str, _params: &Params) -> Result<Solver<'a>> {
+        let mut hands: Vec<Hand<CardLegacy>> = parse_hands(input)?;
+
+        Ok(Box::new(move || {
+            hands.sort();
+            Ok(get_total_winnings(&hands)?.into())
+        }))
+    }
+
+    fn parse_part_two<'a>(&self, input: &'a str, _params: &Params) -> Result<Option<Solver<'a>>> {
+        let mut hands: Vec<Hand<Card>> = parse_hands(input)?;
+
+        Ok(Some(Box::new(move || {
+            hands.sort();
+            Ok(get_total_winnings(&hands)?.into())
+        })))
+    }
+}
+
+pub fn parse_hands<T: CamelCard>(input: &str) -> Result<Vec<Hand<T>>> {
+    numbered_lines(input)
+        .map(|line| {
+            let hand = Hand::from_str(line.text).map_err(|err| err.with_line(line.number))?;
+            trace!(
+                Debug,
+                "line {}: `{}` is {:?}",
+                line.number,
+                line.text,
+                hand.hand_type
+            );
+            Ok(hand)
+        })
+        .collect()
 }
 
-pub fn part_two() {
-    let mut hands: Vec<Hand<Card>> = INPUT
-        .lines()
-        .map(|line| Hand::from_str(line).unwrap())
-        .collect();
-    hands.sort();
-    let sum: u64 = hands
-        .iter()
-        .enumerate()
-        .map(|(k, v)| (k + 1) as u64 * v.bid)
-        .sum();
-
-    println!("7.2 answer: {}", sum);
+pub fn get_total_winnings<T: CamelCard>(hands: &[Hand<T>]) -> Result<u64> {
+    hands.iter().enumerate().try_fold(0u64, |sum, (k, v)| {
+        trace!(
+            Debug,
+            "rank {}: {:?} {:?}, bid {}",
+            k + 1,
+            v.hand_type,
+            v.cards,
+            v.bid
+        );
+        (k as u64 + 1)
+            .checked_mul(v.bid)
+            .and_then(|winnings| sum.checked_add(winnings))
+            .ok_or_else(|| AocError::no_solution("the total winnings do not fit in 64 bits"))
+    })
 }
 
 #[cfg(test)]
 mod tests {
     use std::str::FromStr;
 
-    use super::{Card, CardLegacy, Hand, HandType};
+    use crate::fixtures::examples;
+
+    use super::{parse_hands, Card, CardLegacy, Day07, Hand, HandType};
+
+    examples! {
+        Day07;
+        part_one_works: One, "example1";
+        part_two_works: Two, "example1";
+    }
 
     const EXAMPLE: &str = "32T3K 765
 T55J5 684
@@ -264,17 +332,12 @@ KTJJT 220
 QQQJA 483";
 
     #[test]
-    fn part_one_works() {
+    fn legacy_hands_are_ranked() {
         let mut hands: Vec<Hand<CardLegacy>> = EXAMPLE
             .lines()
             .map(|line| Hand::from_str(line).unwrap())
             .collect();
         hands.sort();
-        let sum: u64 = hands
-            .iter()
-            .enumerate()
-            .map(|(k, v)| (k + 1) as u64 * v.bid)
-            .sum();
 
         assert_eq!(hands[0].hand_type, HandType::OnePair);
         assert_eq!(
@@ -331,21 +394,15 @@ QQQJA 483";
                 CardLegacy::Ace
             ]
         );
-        assert_eq!(sum, 6440);
     }
 
     #[test]
-    fn part_two_works() {
+    fn hands_with_jokers_are_ranked() {
         let mut hands: Vec<Hand<Card>> = EXAMPLE
             .lines()
             .map(|line| Hand::from_str(line).unwrap())
             .collect();
         hands.sort();
-        let sum: u64 = hands
-            .iter()
-            .enumerate()
-            .map(|(k, v)| (k + 1) as u64 * v.bid)
-            .sum();
 
         assert_eq!(hands[0].hand_type, HandType::OnePair);
         assert_eq!(
@@ -372,6 +429,22 @@ QQQJA 483";
             hands[4].cards,
             [Card::King, Card::Ten, Card::Jack, Card::Jack, Card::Ten]
         );
-        assert_eq!(sum, 5905);
+    }
+
+    #[test]
+    fn invalid_hands_are_errors() {
+        let err = parse_hands::<Card>("32T3K 765\nT55X5 684").unwrap_err();
+
+        assert_eq!(
+            format!("{err:#}"),
+            "line 2, column 4: invalid card `X`
+  |
+2 | T55X5 684
+  |    ^"
+        );
+        assert!(Hand::<Card>::from_str("32T3 765").is_err());
+        assert!(Hand::<Card>::from_str("32T3KK 765").is_err());
+        assert!(Hand::<Card>::from_str("32T3K").is_err());
+        assert!(Hand::<Card>::from_str("32T3K bid").is_err());
     }
 }
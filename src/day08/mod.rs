@@ -1,116 +1,219 @@
 use std::collections::HashMap;
 
-const LINE_FEED: u8 = 10;
-const START: Element = *b"AAA";
-const TARGET: Element = *b"ZZZ";
-const INPUT: &[u8] = include_bytes!("./input.txt");
+use crate::{
+    error::{AocError, Result},
+    params::{Param, Params},
+    solution::{Solution, Solver},
+    trace::trace,
+    utils::{byte_error, numbered_lines_from_bytes},
+};
 
-type Element = [u8; 3];
+const PARAMS: &[Param] = &[Param::new("start", "AAA"), Param::new("target", "ZZZ")];
+pub type Element = [u8; 3];
 
 #[derive(Debug, Clone, Copy)]
-enum Direction {
+pub enum Direction {
     Left,
     Right,
 }
 
 impl Direction {
-    fn from_byte(byte: &u8) -> Direction {
+    pub fn from_byte(byte: &u8) -> Option<Direction> {
         match *byte {
-            b'L' => Direction::Left,
-            b'R' => Direction::Right,
-            _ => panic!(),
+            b'L' => Some(Direction::Left),
+            b'R' => Some(Direction::Right),
+            _ => None,
         }
     }
 }
 
-pub fn part_one() {
-    let (directions, node_map) = parse_input(INPUT);
-    println!(
-        "8.1 answer: {}",
-        get_steps_to_zzz(directions.as_slice(), &node_map)
-    );
+pub struct Day08;
+
+impl Solution for Day08 {
+    fn day(&self) -> u8 {
+        8
+    }
+
+    fn title(&self) -> &'static str {
+        "Haunted Wasteland"
+    }
+
+    fn params(&self) -> &'static [Param] {
+        PARAMS
+    }
+
+    fn parse_part_one<'a>(&self, input: &'a str, params: &Params) -> Result<Solver<'a>> {
+        let start = get_element(params, "start")?;
+        let target = get_element(params, "target")?;
+        let (directions, node_map) = parse_input(input.as_bytes())?;
+        Ok(Box::new(move || {
+            Ok(get_steps(directions.as_slice(), &node_map, &start, &target)?.into())
+        }))
+    }
 }
 
-// pub fn part_two() {
-//     todo!()
-// }
+pub type NodeMap = HashMap<Element, (Element, Element)>;
+pub type NumberedLine<'a> = (usize, &'a [u8]);
 
-fn parse_input(input: &[u8]) -> (Vec<Direction>, HashMap<Element, (Element, Element)>) {
-    let mut lines = input.split(|int| *int == LINE_FEED);
-    let directions = parse_directions(lines.next().unwrap());
-    let mut hash_map: HashMap<Element, (Element, Element)> = HashMap::new();
+pub fn parse_input(input: &[u8]) -> Result<(Vec<Direction>, NodeMap)> {
+    let mut lines = numbered_lines_from_bytes(input);
+    let directions = match lines.next() {
+        Some(line) => parse_directions(line)?,
+        None => vec![],
+    };
+    let mut hash_map: NodeMap = HashMap::new();
 
+    if directions.is_empty() {
+        return Err(AocError::parse("expected a line of directions").with_line(1));
+    }
     lines.next();
-    lines
-        .filter(|line| !line.is_empty())
-        .map(parse_node)
-        .for_each(|(key, left, right)| {
-            hash_map.insert(key, (left, right));
-        });
-    (directions, hash_map)
+    for line in lines.filter(|(_, line)| !line.is_empty()) {
+        let (node, left, right) = parse_node(line)?;
+        hash_map.insert(node, (left, right));
+    }
+    Ok((directions, hash_map))
 }
 
-fn get_steps_to_zzz(
+fn get_element(params: &Params, key: &str) -> Result<Element> {
+    let element = params.get_str(key)?;
+    element.as_bytes().try_into().map_err(|_| {
+        AocError::parse(format!(
+            "parameter `{key}` must be a three letter element, got `{element}`"
+        ))
+    })
+}
+
+pub fn get_steps(
     directions: &[Direction],
-    hash_map: &HashMap<Element, (Element, Element)>,
-) -> u64 {
+    hash_map: &NodeMap,
+    start: &Element,
+    target: &Element,
+) -> Result<u64> {
     let mut i = 0;
-    let mut current_element = &START;
+    let mut current_element = start;
     let mut directions_iter = directions.iter();
-
-    while current_element != &TARGET {
+    let max_steps = (directions.len() * hash_map.len()) as u64;
+
+    while current_element != target {
+        if i > max_steps {
+            return Err(AocError::no_solution(format!(
+                "`{}` is not reachable from `{}`",
+                target.escape_ascii(),
+                start.escape_ascii()
+            )));
+        }
         if let Some(direction) = directions_iter.next() {
-            let (left, right) = hash_map.get(current_element).unwrap();
-
-            current_element = match direction {
+            let (left, right) = hash_map.get(current_element).ok_or_else(|| {
+                AocError::no_solution(format!(
+                    "node `{}` is not defined",
+                    current_element.escape_ascii()
+                ))
+            })?;
+
+            let next_element = match direction {
                 Direction::Left => left,
                 Direction::Right => right,
             };
+            trace!(
+                Trace,
+                "step {}: {} going {direction:?} to {}",
+                i + 1,
+                current_element.escape_ascii(),
+                next_element.escape_ascii()
+            );
+            current_element = next_element;
             i += 1;
         } else {
             directions_iter = directions.iter();
         }
     }
-    i
+    trace!(
+        Info,
+        "reached {} from {} in {i} steps",
+        target.escape_ascii(),
+        start.escape_ascii()
+    );
+    Ok(i)
 }
 
-fn parse_directions(line: &[u8]) -> Vec<Direction> {
-    line.iter().map(Direction::from_byte).collect()
+pub fn parse_directions(line: NumberedLine) -> Result<Vec<Direction>> {
+    line.1
+        .iter()
+        .enumerate()
+        .map(|(column, byte)| {
+            Direction::from_byte(byte).ok_or_else(|| {
+                byte_error(
+                    line,
+                    column,
+                    1,
+                    format!("invalid direction `{}`", byte.escape_ascii()),
+                )
+            })
+        })
+        .collect()
 }
 
-fn parse_node(line: &[u8]) -> (Element, Element, Element) {
-    (
-        [line[0], line[1], line[2]],
-        [line[7], line[8], line[9]],
-        [line[12], line[13], line[14]],
-    )
+pub fn parse_node(line: NumberedLine) -> Result<(Element, Element, Element)> {
+    match line.1 {
+        [a, b, c, b' ', b'=', b' ', b'(', d, e, f, b',', b' ', g, h, i, b')'] => {
+            Ok(([*a, *b, *c], [*d, *e, *f], [*g, *h, *i]))
+        }
+        bytes => Err(byte_error(
+            line,
+            0,
+            bytes.len(),
+            "expected `XXX = (YYY, ZZZ)`",
+        )),
+    }
 }
 
 #[cfg(test)]
 mod tests {
-    use crate::day08::{get_steps_to_zzz, parse_input};
+    use crate::{
+        day08::{get_steps, parse_input, Day08},
+        fixtures::examples,
+    };
 
-    const EXAMPLE_ONE: &[u8] = b"RL
+    use super::parse_node;
 
-AAA = (BBB, CCC)
-BBB = (DDD, EEE)
-CCC = (ZZZ, GGG)
-DDD = (DDD, DDD)
-EEE = (EEE, EEE)
-GGG = (GGG, GGG)
-ZZZ = (ZZZ, ZZZ)";
-    const EXAMPLE_TWO: &[u8] = b"LLR
+    examples! {
+        Day08;
+        part_one_works: One, "example1";
+        part_one_repeats_directions: One, "example2";
+    }
 
-AAA = (BBB, BBB)
-BBB = (AAA, ZZZ)
-ZZZ = (ZZZ, ZZZ)";
+    #[test]
+    fn malformed_networks_are_errors() {
+        let err = parse_input(b"LXR\n\nAAA = (BBB, CCC)").unwrap_err();
+        assert_eq!(err.to_string(), "line 1, column 2: invalid direction `X`");
+
+        let err = parse_input(b"LR\n\nAAA = (BBB, CCC)\nBBB = (AAA").unwrap_err();
+        assert_eq!(
+            format!("{err:#}"),
+            "line 4, column 1: expected `XXX = (YYY, ZZZ)`
+  |
+4 | BBB = (AAA
+  | ^^^^^^^^^^"
+        );
+        assert!(parse_node((1, b"AAA = (BBB, CCC")).is_err());
+        assert!(parse_input(b"").is_err());
+    }
 
     #[test]
-    fn part_one_works() {
-        let (directions1, nodemap1) = parse_input(EXAMPLE_ONE);
-        let (directions2, nodemap2) = parse_input(EXAMPLE_TWO);
-        assert_eq!(get_steps_to_zzz(directions1.as_slice(), &nodemap1), 2);
-        assert_eq!(get_steps_to_zzz(directions2.as_slice(), &nodemap2), 6);
+    fn unreachable_targets_are_errors() {
+        let (directions, node_map) =
+            parse_input(b"LR\n\nAAA = (BBB, BBB)\nBBB = (AAA, AAA)\nZZZ = (ZZZ, ZZZ)").unwrap();
+
+        assert_eq!(
+            get_steps(&directions, &node_map, b"AAA", b"BBB").unwrap(),
+            1
+        );
+        assert_eq!(
+            get_steps(&directions, &node_map, b"AAA", b"ZZZ")
+                .unwrap_err()
+                .to_string(),
+            "no solution: `ZZZ` is not reachable from `AAA`"
+        );
     }
 
     // #[test]
@@ -1,55 +1,98 @@
 use std::collections::VecDeque;
 
-const INPUT: &str = include_str!("./input.txt");
+use crate::{
+    error::{AocError, Result},
+    params::Params,
+    solution::{Solution, Solver},
+    utils::{numbered_lines, Line},
+};
 
-pub fn part_one() {
-    let number_vecs = parse_input(INPUT);
-    let mut differences_deques: Vec<Vec<VecDeque<i64>>> = number_vecs
-        .iter()
-        .map(|nums| get_with_differences(nums))
-        .collect();
+pub struct Day09;
 
-    differences_deques
-        .iter_mut()
-        .for_each(|diff_deque| push_values_back(diff_deque));
-    let history_values: Vec<i64> = differences_deques
-        .iter()
-        .map(|diff_deque| get_right_history_value(diff_deque))
-        .collect();
-    println!("9.1 answer: {}", history_values.iter().sum::<i64>());
-}
+impl Solution for Day09 {
+    fn day(&self) -> u8 {
+        9
+    }
 
-pub fn part_two() {
-    let number_vecs = parse_input(INPUT);
-    let mut differences_deques: Vec<Vec<VecDeque<i64>>> = number_vecs
-        .iter()
-        .map(|nums| get_with_differences(nums))
-        .collect();
+    fn title(&self) -> &'static str {
+        "Mirage Maintenance"
+    }
 
-    differences_deques
-        .iter_mut()
-        .for_each(|diff_deque| push_values_front(diff_deque));
-    let history_values: Vec<i64> = differences_deques
-        .iter()
-        .map(|diff_deque| get_left_history_value(diff_deque))
-        .collect();
-    println!("9.2 answer: {}", history_values.iter().sum::<i64>());
+    fn parse_part_one<'a>(&self, input: &'a str, _params: &Params) -> Result<Solver<'a>> {
+        let number_vecs = parse_input(input)?;
+
+        Ok(Box::new(move || {
+            let mut differences_deques: Vec<Vec<VecDeque<i64>>> = number_vecs
+                .iter()
+                .map(|nums| get_with_differences(nums))
+                .collect::<Result<_>>()?;
+
+            differences_deques
+                .iter_mut()
+                .try_for_each(|diff_deque| push_values_back(diff_deque))?;
+            let history_values: Vec<i64> = differences_deques
+                .iter()
+                .map(|diff_deque| get_right_history_value(diff_deque))
+                .collect();
+            Ok(history_values
+                .iter()
+                .try_fold(0i64, |sum, value| sum.checked_add(*value))
+                .ok_or_else(history_overflow)?
+                .into())
+        }))
+    }
+
+    fn parse_part_two<'a>(&self, input: &'a str, _params: &Params) -> Result<Option<Solver<'a>>> {
+        let number_vecs = parse_input(input)?;
+
+        Ok(Some(Box::new(move || {
+            let mut differences_deques: Vec<Vec<VecDeque<i64>>> = number_vecs
+                .iter()
+                .map(|nums| get_with_differences(nums))
+                .collect::<Result<_>>()?;
+
+            differences_deques
+                .iter_mut()
+                .try_for_each(|diff_deque| push_values_front(diff_deque))?;
+            let history_values: Vec<i64> = differences_deques
+                .iter()
+                .map(|diff_deque| get_left_history_value(diff_deque))
+                .collect();
+            Ok(history_values
+                .iter()
+                .try_fold(0i64, |sum, value| sum.checked_add(*value))
+                .ok_or_else(history_overflow)?
+                .into())
+        })))
+    }
 }
 
-fn parse_input(input: &str) -> Vec<Vec<i64>> {
-    input
-        .lines()
-        .filter(|line| !line.is_empty())
-        .map(|line| {
-            line.trim_end()
-                .split_ascii_whitespace()
-                .map(|num_str| num_str.parse().unwrap())
-                .collect()
-        })
+pub fn parse_input(input: &str) -> Result<Vec<Vec<i64>>> {
+    numbered_lines(input)
+        .filter(|line| !line.text.is_empty())
+        .map(parse_history)
         .collect()
 }
 
-fn get_with_differences(nums: &[i64]) -> Vec<VecDeque<i64>> {
+pub fn parse_history(line: Line) -> Result<Vec<i64>> {
+    let history = line
+        .text
+        .trim_end()
+        .split_ascii_whitespace()
+        .map(|num_str| {
+            num_str
+                .parse()
+                .map_err(|_| line.error_at(num_str, format!("invalid number `{num_str}`")))
+        })
+        .collect::<Result<Vec<i64>>>()?;
+
+    if history.is_empty() {
+        return Err(line.error("expected at least one number"));
+    }
+    Ok(history)
+}
+
+pub fn get_with_differences(nums: &[i64]) -> Result<Vec<VecDeque<i64>>> {
     let mut curr = nums;
     let mut first_deque: VecDeque<i64> = VecDeque::with_capacity(curr.len() * 2 - 1);
     let mut diff_deques = Vec::with_capacity(curr.len());
@@ -59,50 +102,60 @@ fn get_with_differences(nums: &[i64]) -> Vec<VecDeque<i64>> {
     first_vec.push(..nums);
     diff_deques.push(first_deque);
     while curr.len() > 1 && !curr.iter().all(|n| *n == 0) {
-        let mut differences = get_differences(curr);
+        let mut differences = get_differences(curr)?;
         differences.make_contiguous();
         diff_deques.push(differences);
         (curr, _) = diff_deques.last().unwrap().as_slices();
     }
-    diff_deques
+    Ok(diff_deques)
 }
 
-fn get_differences(nums: &[i64]) -> VecDeque<i64> {
+pub fn get_differences(nums: &[i64]) -> Result<VecDeque<i64>> {
     let len = nums.len();
     let mut diff_deque = VecDeque::with_capacity(len * 2 - 1);
 
     for i in 1..len {
-        diff_deque.push_back(unsafe { nums.get_unchecked(i) - nums.get_unchecked(i - 1) });
+        let difference = unsafe {
+            nums.get_unchecked(i)
+                .checked_sub(*nums.get_unchecked(i - 1))
+        };
+        diff_deque.push_back(difference.ok_or_else(history_overflow)?);
     }
-    diff_deque
+    Ok(diff_deque)
 }
 
-fn push_values_back(num_deques: &mut [VecDeque<i64>]) {
-    let mut below = 0;
+pub fn push_values_back(num_deques: &mut [VecDeque<i64>]) -> Result<()> {
+    let mut below = 0i64;
 
     for num_deque in num_deques.iter_mut().rev() {
         let len = num_deque.len();
         let last = num_deque[len - 1];
-        let next = last + below;
+        let next = last.checked_add(below).ok_or_else(history_overflow)?;
 
         num_deque.push_back(next);
         below = next;
     }
+    Ok(())
 }
 
-fn push_values_front(num_deques: &mut [VecDeque<i64>]) {
-    let mut below = 0;
+pub fn push_values_front(num_deques: &mut [VecDeque<i64>]) -> Result<()> {
+    let mut below = 0i64;
 
     for num_deque in num_deques.iter_mut().rev() {
         let first = num_deque[0];
-        let prev = first - below;
+        let prev = first.checked_sub(below).ok_or_else(history_overflow)?;
 
         num_deque.push_front(prev);
         below = prev;
     }
+    Ok(())
+}
+
+fn history_overflow() -> AocError {
+    AocError::no_solution("history values do not fit in 64 bits")
 }
 
-fn get_right_history_value(num_deques: &[VecDeque<i64>]) -> i64 {
+pub fn get_right_history_value(num_deques: &[VecDeque<i64>]) -> i64 {
     num_deques
         .iter()
         .map(|num_deque| *num_deque.back().unwrap())
@@ -110,7 +163,7 @@ fn get_right_history_value(num_deques: &[VecDeque<i64>]) -> i64 {
         .unwrap_or(0)
 }
 
-fn get_left_history_value(num_deques: &[VecDeque<i64>]) -> i64 {
+pub fn get_left_history_value(num_deques: &[VecDeque<i64>]) -> i64 {
     num_deques
         .iter()
         .map(|num_deque| *num_deque.front().unwrap())
@@ -122,45 +175,58 @@ fn get_left_history_value(num_deques: &[VecDeque<i64>]) -> i64 {
 mod tests {
     use std::collections::VecDeque;
 
-    use crate::day09::get_left_history_value;
+    use crate::{
+        day09::{get_left_history_value, Day09},
+        fixtures::examples,
+    };
 
     use super::{
         get_right_history_value, get_with_differences, parse_input, push_values_back,
         push_values_front,
     };
+
+    examples! {
+        Day09;
+        part_one_works: One, "example1";
+        part_two_works: Two, "example1";
+    }
+
     const EXAMPLE: &str = "0 3 6 9 12 15
 1 3 6 10 15 21
 10 13 16 21 30 45";
 
     #[test]
-    fn part_one_works() {
-        let number_vecs = parse_input(EXAMPLE);
+    fn right_history_values_are_extrapolated() {
+        let number_vecs = parse_input(EXAMPLE).unwrap();
         let mut differences_deques: Vec<Vec<VecDeque<i64>>> = number_vecs
             .iter()
-            .map(|nums| get_with_differences(nums))
+            .map(|nums| get_with_differences(nums).unwrap())
             .collect();
         let final_match1 = parse_input(
             "0   3   6   9  12  15  18
   3   3   3   3   3   3
     0   0   0   0   0",
-        );
+        )
+        .unwrap();
         let final_match2 = parse_input(
             "1   3   6  10  15  21  28
   2   3   4   5   6   7
     1   1   1   1   1
       0   0   0   0",
-        );
+        )
+        .unwrap();
         let final_match3 = parse_input(
             "10  13  16  21  30  45  68
    3   3   5   9  15  23
      0   2   4   6   8
        2   2   2   2
          0   0   0",
-        );
+        )
+        .unwrap();
 
         differences_deques
             .iter_mut()
-            .for_each(|diff_deque| push_values_back(diff_deque));
+            .for_each(|diff_deque| push_values_back(diff_deque).unwrap());
         let history_values: Vec<i64> = differences_deques
             .iter()
             .map(|diff_deque| get_right_history_value(diff_deque))
@@ -169,34 +235,36 @@ mod tests {
         assert_eq!(differences_deques[1], final_match2);
         assert_eq!(differences_deques[2], final_match3);
         assert_eq!(history_values.as_slice(), &[18, 28, 68]);
-        assert_eq!(history_values.iter().sum::<i64>(), 114);
     }
 
     #[test]
     fn differences_are_correct() {
-        let number_vecs = parse_input(EXAMPLE);
+        let number_vecs = parse_input(EXAMPLE).unwrap();
         let differences_deques: Vec<Vec<VecDeque<i64>>> = number_vecs
             .iter()
-            .map(|nums| get_with_differences(nums))
+            .map(|nums| get_with_differences(nums).unwrap())
             .collect();
         let differences_match1 = parse_input(
             "0   3   6   9  12  15
   3   3   3   3   3
     0   0   0   0",
-        );
+        )
+        .unwrap();
         let differences_match2 = parse_input(
             "1   3   6  10  15  21
   2   3   4   5   6
     1   1   1   1
       0   0   0",
-        );
+        )
+        .unwrap();
         let differences_match3 = parse_input(
             "10  13  16  21  30  45
    3   3   5   9  15
      0   2   4   6
        2   2   2
          0   0",
-        );
+        )
+        .unwrap();
 
         assert_eq!(differences_deques[0], differences_match1);
         assert_eq!(differences_deques[1], differences_match2);
@@ -204,11 +272,11 @@ mod tests {
     }
 
     #[test]
-    fn part_two_works() {
-        let number_vecs = parse_input(EXAMPLE);
+    fn left_history_values_are_extrapolated() {
+        let number_vecs = parse_input(EXAMPLE).unwrap();
         let mut differences_deques: Vec<Vec<VecDeque<i64>>> = number_vecs
             .iter()
-            .map(|nums| get_with_differences(nums))
+            .map(|nums| get_with_differences(nums).unwrap())
             .collect();
         let differences_match3 = parse_input(
             "5  10  13  16  21  30  45
@@ -216,30 +284,40 @@ mod tests {
    -2   0   2   4   6
       2   2   2   2
         0   0   0",
-        );
+        )
+        .unwrap();
 
         differences_deques
             .iter_mut()
-            .for_each(|diff_deque| push_values_front(diff_deque));
+            .for_each(|diff_deque| push_values_front(diff_deque).unwrap());
         let history_values: Vec<i64> = differences_deques
             .iter()
             .map(|diff_vec| get_left_history_value(diff_vec))
             .collect();
         assert_eq!(differences_deques[2], differences_match3);
         assert_eq!(history_values.as_slice(), &[-3, 0, 5]);
-        assert_eq!(history_values.iter().sum::<i64>(), 2);
     }
 
     #[test]
     fn differences_are_correct_when_negatives() {
-        let number_vecs = parse_input("-12 -9 -6 -3 0 3");
+        let number_vecs = parse_input("-12 -9 -6 -3 0 3").unwrap();
         let differences_match = parse_input(
             "-12 -9 -6 -3 0 3
                 3 3 3 3 3
                 0 0 0 0",
-        );
-        let differences_deque: Vec<VecDeque<i64>> = get_with_differences(number_vecs[0].as_slice());
+        )
+        .unwrap();
+        let differences_deque: Vec<VecDeque<i64>> =
+            get_with_differences(number_vecs[0].as_slice()).unwrap();
 
         assert_eq!(differences_deque, differences_match);
     }
+
+    #[test]
+    fn invalid_histories_are_errors() {
+        let err = parse_input("0 3 6\n1 x 6").unwrap_err();
+
+        assert_eq!(err.to_string(), "line 2, column 3: invalid number `x`");
+        assert!(parse_input("0 3 6\n   \n").is_err());
+    }
 }
@@ -1,9 +1,13 @@
-use crate::utils::parse_from_bytes;
-
-const INPUT: &[u8] = include_bytes!("./input.txt");
+use crate::{
+    error::{AocError, Result},
+    params::Params,
+    solution::{Solution, Solver},
+    trace::trace,
+    utils::{byte_error, numbered_lines_from_bytes},
+};
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
-enum Pipe {
+pub enum Pipe {
     NorthSouth,
     EastWest,
     NorthEast,
@@ -15,23 +19,23 @@ enum Pipe {
 }
 
 impl Pipe {
-    fn from_byte(byte: u8) -> Self {
+    pub fn from_byte(byte: u8) -> Option<Self> {
         match byte {
-            b'|' => Self::NorthSouth,
-            b'-' => Self::EastWest,
-            b'L' => Self::NorthEast,
-            b'J' => Self::NorthWest,
-            b'7' => Self::SouthWest,
-            b'F' => Self::SouthEast,
-            b'.' => Self::Ground,
-            b'S' => Self::Start,
-            _ => panic!(),
+            b'|' => Some(Self::NorthSouth),
+            b'-' => Some(Self::EastWest),
+            b'L' => Some(Self::NorthEast),
+            b'J' => Some(Self::NorthWest),
+            b'7' => Some(Self::SouthWest),
+            b'F' => Some(Self::SouthEast),
+            b'.' => Some(Self::Ground),
+            b'S' => Some(Self::Start),
+            _ => None,
         }
     }
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
-enum Direction {
+pub enum Direction {
     North,
     West,
     East,
@@ -39,7 +43,7 @@ enum Direction {
 }
 
 impl Direction {
-    fn next_from_pipe(&self, next_pipe: &Pipe) -> Option<Self> {
+    pub fn next_from_pipe(&self, next_pipe: &Pipe) -> Option<Self> {
         match next_pipe {
             Pipe::NorthSouth => match self {
                 Self::East | Self::West => None,
@@ -74,25 +78,50 @@ impl Direction {
     }
 }
 
-pub fn part_one() {
-    let pipes = parse_input(INPUT);
-    let furthest = get_furthest_path_steps(pipes.as_slice());
+pub struct Day10;
 
-    println!("10.1 answer: {}", furthest);
-}
+impl Solution for Day10 {
+    fn day(&self) -> u8 {
+        10
+    }
 
-// pub fn part_two() {
-//     todo!()
-// }
+    fn title(&self) -> &'static str {
+        "Pipe Maze"
+    }
+
+    fn parse_part_one<'a>(&self, input: &'a str, _params: &Params) -> Result<Solver<'a>> {
+        let pipes = parse_input(input.as_bytes())?;
+        Ok(Box::new(move || {
+            Ok(get_furthest_path_steps(pipes.as_slice())?.into())
+        }))
+    }
+}
 
-fn parse_input(input: &[u8]) -> Vec<Vec<Pipe>> {
-    parse_from_bytes(input)
-        .map(|line| line.map(Pipe::from_byte).collect())
+pub fn parse_input(input: &[u8]) -> Result<Vec<Vec<Pipe>>> {
+    numbered_lines_from_bytes(input)
+        .filter(|(_, line)| !line.is_empty())
+        .map(|line| {
+            line.1
+                .iter()
+                .enumerate()
+                .map(|(column, byte)| {
+                    Pipe::from_byte(*byte).ok_or_else(|| {
+                        byte_error(
+                            line,
+                            column,
+                            1,
+                            format!("invalid pipe `{}`", byte.escape_ascii()),
+                        )
+                    })
+                })
+                .collect()
+        })
         .collect()
 }
 
-fn get_furthest_path_steps(pipes: &[Vec<Pipe>]) -> u64 {
-    let (start_y, start_x) = find_start(pipes);
+pub fn get_furthest_path_steps(pipes: &[Vec<Pipe>]) -> Result<u64> {
+    let (start_y, start_x) =
+        find_start(pipes).ok_or_else(|| AocError::no_solution("no start tile `S`"))?;
     let start_directions = [
         Direction::North,
         Direction::West,
@@ -104,11 +133,11 @@ fn get_furthest_path_steps(pipes: &[Vec<Pipe>]) -> u64 {
         .iter()
         .filter_map(|dir| get_steps_to_loop(pipes, start_y, start_x, *dir))
         .max()
-        .unwrap()
-        / 2
+        .map(|steps| steps / 2)
+        .ok_or_else(|| AocError::no_solution("no loop passes through the start tile"))
 }
 
-fn get_steps_to_loop(
+pub fn get_steps_to_loop(
     pipes: &[Vec<Pipe>],
     start_y: usize,
     start_x: usize,
@@ -123,18 +152,32 @@ fn get_steps_to_loop(
             y = pipe_y;
             x = pipe_x;
             direction = dir.next_from_pipe(&pipes[y][x]);
+            trace!(
+                Trace,
+                "step {}: {dir:?} to ({y}, {x}) {:?}",
+                step_count + 1,
+                pipes[y][x]
+            );
         } else {
             direction = None;
         }
         step_count += 1;
         if pipes[y][x] == Pipe::Start {
+            trace!(
+                Info,
+                "leaving {start_dir:?} closes the loop in {step_count} steps"
+            );
             return Some(step_count);
         }
     }
+    trace!(
+        Info,
+        "leaving {start_dir:?} dead-ends after {step_count} steps"
+    );
     None
 }
 
-fn get_next_step(
+pub fn get_next_step(
     pipes: &[Vec<Pipe>],
     direction: Direction,
     y: usize,
@@ -157,23 +200,27 @@ fn get_next_step(
     None
 }
 
-fn find_start(pipes: &[Vec<Pipe>]) -> (usize, usize) {
-    pipes
-        .iter()
-        .enumerate()
-        .find_map(|line| {
-            line.1
-                .iter()
-                .enumerate()
-                .find(|pipe| pipe.1 == &Pipe::Start)
-                .map(|start| (line.0, start.0))
-        })
-        .unwrap()
+pub fn find_start(pipes: &[Vec<Pipe>]) -> Option<(usize, usize)> {
+    pipes.iter().enumerate().find_map(|line| {
+        line.1
+            .iter()
+            .enumerate()
+            .find(|pipe| pipe.1 == &Pipe::Start)
+            .map(|start| (line.0, start.0))
+    })
 }
 
 #[cfg(test)]
 mod tests {
-    use super::{find_start, get_furthest_path_steps, parse_input};
+    use crate::fixtures::examples;
+
+    use super::{find_start, get_furthest_path_steps, parse_input, Day10};
+
+    examples! {
+        Day10;
+        part_one_works: One, "example1";
+        part_one_skips_unconnected_pipes: One, "example2";
+    }
 
     const EXAMPLE1: &[u8] = b"-L|F7
 7S-7|
@@ -187,25 +234,23 @@ SJLL7
 |F--J
 LJ.LJ";
 
-    #[test]
-    fn part_one_works() {
-        let pipes1 = parse_input(EXAMPLE1);
-        let pipes2 = parse_input(EXAMPLE2);
-        let furthest1 = get_furthest_path_steps(pipes1.as_slice());
-        let furthest2 = get_furthest_path_steps(pipes2.as_slice());
-
-        assert_eq!(furthest1, 4);
-        assert_eq!(furthest2, 8);
-    }
-
     #[test]
     fn find_start_works() {
-        let pipes1 = parse_input(EXAMPLE1);
-        let pipes2 = parse_input(EXAMPLE2);
+        let pipes1 = parse_input(EXAMPLE1).unwrap();
+        let pipes2 = parse_input(EXAMPLE2).unwrap();
         let start1 = find_start(pipes1.as_slice());
         let start2 = find_start(pipes2.as_slice());
 
-        assert_eq!(start1, (1, 1));
-        assert_eq!(start2, (2, 0));
+        assert_eq!(start1, Some((1, 1)));
+        assert_eq!(start2, Some((2, 0)));
+    }
+
+    #[test]
+    fn invalid_maps_are_errors() {
+        let err = parse_input(b"-L|F7\n7S-X|").unwrap_err();
+        assert_eq!(err.to_string(), "line 2, column 4: invalid pipe `X`");
+
+        let pipes = parse_input(b"-L|F7\n7.-7|").unwrap();
+        assert!(get_furthest_path_steps(pipes.as_slice()).is_err());
     }
 }
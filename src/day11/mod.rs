@@ -1,33 +1,113 @@
-const INPUT: &[u8] = include_bytes!("./input.txt");
+pub type GalaxyNumberAndXY = (usize, (u128, u128));
+pub type GalaxyPair = (GalaxyNumberAndXY, GalaxyNumberAndXY);
+pub type ExpandedImage = Vec<(u128, Vec<(u128, bool)>)>;
 
-type GalaxyNumberAndXY = (usize, (u128, u128));
-type GalaxyPair = (GalaxyNumberAndXY, GalaxyNumberAndXY);
+use crate::{
+    error::{AocError, Result},
+    params::{Param, Params},
+    solution::{Answer, Solution, Solver},
+    utils::{byte_error, numbered_lines_from_bytes},
+};
 
-use crate::utils::parse_from_bytes;
+const PARAMS: &[Param] = &[
+    Param::new("part1_expansion", "2"),
+    Param::new("part2_expansion", "1000000"),
+];
 
-pub fn part_one() {
-    let galaxy_matrix = parse_with_expansion(INPUT, 2);
-    let galaxy_pairs = get_galaxy_pairs(galaxy_matrix.as_slice());
-    let steps_betwixt: Vec<u128> = galaxy_pairs.iter().map(get_steps).collect();
+pub struct Day11;
 
-    println!("11.1 answer: {}", steps_betwixt.iter().sum::<u128>());
+impl Solution for Day11 {
+    fn day(&self) -> u8 {
+        11
+    }
+
+    fn title(&self) -> &'static str {
+        "Cosmic Expansion"
+    }
+
+    fn params(&self) -> &'static [Param] {
+        PARAMS
+    }
+
+    fn parse_part_one<'a>(&self, input: &'a str, params: &Params) -> Result<Solver<'a>> {
+        let expansion = get_expansion(params, "part1_expansion")?;
+        let galaxy_matrix = parse_with_expansion(input.as_bytes(), expansion.into())?;
+
+        Ok(Box::new(move || {
+            let galaxy_pairs = get_galaxy_pairs(galaxy_matrix.as_slice());
+            let steps_betwixt: Vec<u128> = galaxy_pairs.iter().map(get_steps).collect();
+
+            to_answer(steps_betwixt.iter().sum::<u128>())
+        }))
+    }
+
+    fn parse_part_two<'a>(&self, input: &'a str, params: &Params) -> Result<Option<Solver<'a>>> {
+        let expansion = get_expansion(params, "part2_expansion")?;
+        let galaxy_matrix = parse_with_expansion(input.as_bytes(), expansion.into())?;
+
+        Ok(Some(Box::new(move || {
+            let galaxy_pairs = get_galaxy_pairs(galaxy_matrix.as_slice());
+            let steps_betwixt: Vec<u128> = galaxy_pairs.iter().map(get_steps).collect();
+
+            to_answer(steps_betwixt.iter().sum::<u128>())
+        })))
+    }
 }
 
-pub fn part_two() {
-    let galaxy_matrix = parse_with_expansion(INPUT, 1000000);
-    let galaxy_pairs = get_galaxy_pairs(galaxy_matrix.as_slice());
-    let steps_betwixt: Vec<u128> = galaxy_pairs.iter().map(get_steps).collect();
+fn get_expansion(params: &Params, key: &str) -> Result<u64> {
+    match params.get_u64(key)? {
+        0 => Err(AocError::parse(format!(
+            "parameter `{key}` must be at least 1"
+        ))),
+        expansion => Ok(expansion),
+    }
+}
 
-    println!("11.1 answer: {}", steps_betwixt.iter().sum::<u128>());
+pub fn to_answer(sum: u128) -> Result<Answer> {
+    u64::try_from(sum)
+        .map(Answer::from)
+        .map_err(|_| AocError::no_solution(format!("sum of steps {sum} does not fit in 64 bits")))
 }
 
-fn parse_input(input: &[u8]) -> Vec<Vec<bool>> {
-    parse_from_bytes(input)
-        .map(|line| line.map(|c| c == b'#').collect())
-        .collect()
+pub fn parse_input(input: &[u8]) -> Result<Vec<Vec<bool>>> {
+    let mut parsed: Vec<Vec<bool>> = vec![];
+
+    for line in numbered_lines_from_bytes(input).filter(|(_, line)| !line.is_empty()) {
+        let width = parsed.first().map_or(line.1.len(), Vec::len);
+        let are_galaxies = line
+            .1
+            .iter()
+            .enumerate()
+            .map(|(column, c)| match c {
+                b'#' => Ok(true),
+                b'.' => Ok(false),
+                _ => Err(byte_error(
+                    line,
+                    column,
+                    1,
+                    format!("invalid pixel `{}`", c.escape_ascii()),
+                )),
+            })
+            .collect::<Result<Vec<bool>>>()?;
+
+        if are_galaxies.len() != width {
+            return Err(byte_error(
+                line,
+                0,
+                line.1.len(),
+                format!("expected {width} pixels but found {}", are_galaxies.len()),
+            ));
+        }
+        parsed.push(are_galaxies);
+    }
+    if parsed.is_empty() {
+        return Err(AocError::parse("image is empty"));
+    }
+    Ok(parsed)
 }
-fn parse_with_expansion(input: &[u8], expansion_size: u128) -> Vec<(u128, Vec<(u128, bool)>)> {
-    let parsed = parse_input(input);
+
+pub fn parse_with_expansion(input: &[u8], expansion_size: u128) -> Result<ExpandedImage> {
+    let parsed = parse_input(input)?;
     let count_y = parsed.len();
     let count_x = parsed[0].len();
 
@@ -70,11 +150,11 @@ fn parse_with_expansion(input: &[u8], expansion_size: u128) -> Vec<(u128, Vec<(u
         true_y += 1;
     }
 
-    are_galaxies_vecs
+    Ok(are_galaxies_vecs)
 }
 
-fn _legacy_parse(input: &[u8]) -> Vec<Vec<bool>> {
-    let parsed = parse_input(input);
+pub fn legacy_parse(input: &[u8]) -> Result<Vec<Vec<bool>>> {
+    let parsed = parse_input(input)?;
     let count_y = parsed.len();
     let count_x = parsed[0].len();
 
@@ -118,10 +198,10 @@ fn _legacy_parse(input: &[u8]) -> Vec<Vec<bool>> {
         are_galaxies_vecs.push(are_galaxies);
     }
 
-    are_galaxies_vecs
+    Ok(are_galaxies_vecs)
 }
 
-fn get_galaxy_pairs(are_galaxies: &[(u128, Vec<(u128, bool)>)]) -> Vec<GalaxyPair> {
+pub fn get_galaxy_pairs(are_galaxies: &[(u128, Vec<(u128, bool)>)]) -> Vec<GalaxyPair> {
     let galaxies_yx: Vec<GalaxyNumberAndXY> = are_galaxies
         .iter()
         .flat_map(|(y, row)| {
@@ -137,7 +217,7 @@ fn get_galaxy_pairs(are_galaxies: &[(u128, Vec<(u128, bool)>)]) -> Vec<GalaxyPai
     let mut pairs = Vec::with_capacity(len / 2);
 
     let mut i = 0;
-    while i < len - 1 {
+    while i + 1 < len {
         let mut j = i + 1;
         while j < len {
             pairs.push((galaxies_yx[i], galaxies_yx[j]));
@@ -148,17 +228,29 @@ fn get_galaxy_pairs(are_galaxies: &[(u128, Vec<(u128, bool)>)]) -> Vec<GalaxyPai
     pairs
 }
 
-fn get_steps(galaxy_pair: &GalaxyPair) -> u128 {
+pub fn get_steps(galaxy_pair: &GalaxyPair) -> u128 {
     let ((_, (y1, x1)), (_, (y2, x2))) = galaxy_pair;
     y2.abs_diff(*y1) + x2.abs_diff(*x1)
 }
 
 #[cfg(test)]
 mod tests {
-    use crate::day11::{
-        _legacy_parse, get_galaxy_pairs, get_steps, parse_input, parse_with_expansion,
+    use crate::{
+        day11::{
+            get_galaxy_pairs, get_steps, legacy_parse, parse_input, parse_with_expansion, Day11,
+        },
+        fixtures::examples,
+        params::Params,
+        solution::Solution,
+        toml::Value,
     };
 
+    examples! {
+        Day11;
+        part_one_works: One, "example1";
+        part_two_works: Two, "example1";
+    }
+
     const EXAMPLE: &[u8] = b"...#......
 .......#..
 #.........
@@ -183,21 +275,17 @@ mod tests {
 #....#.......";
 
     #[test]
-    fn part_one_works() {
-        let galaxy_matrix = parse_with_expansion(EXAMPLE, 2);
-        let already_expanded = parse_input(EXAMPLE_EXPANDED);
-        let runtime_expanded: Vec<Vec<bool>> = _legacy_parse(EXAMPLE);
-        let galaxy_pairs = get_galaxy_pairs(galaxy_matrix.as_slice());
-        let steps_betwixt: Vec<u128> = galaxy_pairs.iter().map(get_steps).collect();
+    fn images_are_expanded() {
+        let already_expanded = parse_input(EXAMPLE_EXPANDED).unwrap();
+        let runtime_expanded: Vec<Vec<bool>> = legacy_parse(EXAMPLE).unwrap();
 
         assert_eq!(already_expanded, runtime_expanded);
-        assert_eq!(steps_betwixt.iter().sum::<u128>(), 374);
     }
 
     #[test]
-    fn part_two_works() {
-        let galaxy_matrix1 = parse_with_expansion(EXAMPLE, 10);
-        let galaxy_matrix2 = parse_with_expansion(EXAMPLE, 100);
+    fn smaller_expansions_work() {
+        let galaxy_matrix1 = parse_with_expansion(EXAMPLE, 10).unwrap();
+        let galaxy_matrix2 = parse_with_expansion(EXAMPLE, 100).unwrap();
         let galaxy_pairs1 = get_galaxy_pairs(galaxy_matrix1.as_slice());
         let galaxy_pairs2 = get_galaxy_pairs(galaxy_matrix2.as_slice());
         let steps_betwixt1: Vec<u128> = galaxy_pairs1.iter().map(get_steps).collect();
@@ -206,4 +294,32 @@ mod tests {
         assert_eq!(steps_betwixt1.iter().sum::<u128>(), 1030);
         assert_eq!(steps_betwixt2.iter().sum::<u128>(), 8410);
     }
+
+    #[test]
+    fn zero_expansions_are_errors() {
+        let mut params = Params::new(Day11.params());
+        params.set("part1_expansion", Value::Integer(0)).unwrap();
+
+        let err = Day11.part_one("#.\n..\n", &params).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "parameter `part1_expansion` must be at least 1"
+        );
+        assert!(Day11
+            .part_one("#.\n..\n", &Params::new(Day11.params()))
+            .is_ok());
+    }
+
+    #[test]
+    fn malformed_images_are_errors() {
+        let err = parse_input(b"..#.\n.x..").unwrap_err();
+        assert_eq!(err.to_string(), "line 2, column 2: invalid pixel `x`");
+
+        let err = parse_input(b"..#.\n...").unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "line 2, column 1: expected 4 pixels but found 3"
+        );
+        assert!(parse_input(b"").is_err());
+    }
 }
@@ -0,0 +1,272 @@
+use std::{
+    fmt::Debug,
+    fs,
+    path::{Path, PathBuf},
+};
+
+use crate::{
+    day01::{get_calibration_number, get_calibration_number_legacy},
+    day06::{parse_races, MarginOfError, Race},
+    day11::{legacy_parse, parse_with_expansion},
+    error::Result,
+    generate::generate,
+    rng::Rng,
+    robustness::catch_panic,
+};
+
+pub const CASES_DIR: &str = "differential";
+pub const DEFAULT_CASES: u32 = 200;
+
+const MAX_RACES: usize = 4;
+const MAX_RACE_TIME: u64 = 10_000;
+
+pub struct Pair {
+    pub name: &'static str,
+    pub day: u8,
+    pub generate: fn(usize, &mut Rng) -> String,
+    pub reference: fn(&str) -> String,
+    pub optimized: fn(&str) -> String,
+}
+
+pub static PAIRS: &[Pair] = &[
+    Pair {
+        name: "day01-calibration",
+        day: 1,
+        generate: digit_only_calibration_lines,
+        reference: |input| describe_lines(input, get_calibration_number_legacy),
+        optimized: |input| describe_lines(input, get_calibration_number),
+    },
+    Pair {
+        name: "day06-margin",
+        day: 6,
+        generate: race_sheet,
+        reference: |input| describe_margins(input, |race| race.get_margin_of_error_linear()),
+        optimized: |input| describe_margins(input, |race| race.get_margin_of_error()),
+    },
+    Pair {
+        name: "day11-expansion",
+        day: 11,
+        generate: |size, rng| generate(11, size, rng).unwrap_or_default(),
+        reference: |input| describe(legacy_galaxies(input)),
+        optimized: |input| describe(expanded_galaxies(input)),
+    },
+];
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Divergence {
+    pub name: &'static str,
+    pub case: u32,
+    pub input: String,
+    pub reference: String,
+    pub optimized: String,
+}
+
+pub fn get(name: &str) -> Option<&'static Pair> {
+    PAIRS.iter().find(|pair| pair.name == name)
+}
+
+pub fn cross_check(pair: &Pair, cases: u32, max_size: usize, rng: &mut Rng) -> Option<Divergence> {
+    for case in 0..cases {
+        let size = 1 + (max_size - 1) * case as usize / cases.max(2) as usize;
+        let input = (pair.generate)(size, rng);
+
+        if let Some((reference, optimized)) = diverges(pair, &input) {
+            return Some(Divergence {
+                name: pair.name,
+                case,
+                input,
+                reference,
+                optimized,
+            });
+        }
+    }
+    None
+}
+
+pub fn diverges(pair: &Pair, input: &str) -> Option<(String, String)> {
+    let describe = |output: std::result::Result<String, String>| {
+        output.unwrap_or_else(|message| format!("panicked: {message}"))
+    };
+
+    match (
+        catch_panic(|| (pair.reference)(input)),
+        catch_panic(|| (pair.optimized)(input)),
+    ) {
+        (Ok(reference), Ok(optimized)) if reference == optimized => None,
+        (reference, optimized) => Some((describe(reference), describe(optimized))),
+    }
+}
+
+pub fn write_case(fixtures: &Path, pair: &Pair, input: &str) -> Result<PathBuf> {
+    let dir = fixtures.join(CASES_DIR);
+    fs::create_dir_all(&dir)?;
+
+    let path = (1..)
+        .map(|key| dir.join(format!("{}-{key}.txt", pair.name)))
+        .find(|path| !path.exists())
+        .expect("there is always a free file name");
+    fs::write(&path, input)?;
+    Ok(path)
+}
+
+pub fn load_cases(fixtures: &Path) -> Result<Vec<(&'static Pair, PathBuf, String)>> {
+    let dir = fixtures.join(CASES_DIR);
+    let mut cases = vec![];
+
+    if !dir.exists() {
+        return Ok(cases);
+    }
+    for entry in fs::read_dir(&dir)? {
+        let path = entry?.path();
+        let pair = path
+            .file_stem()
+            .and_then(|stem| stem.to_str())
+            .and_then(|stem| stem.rsplit_once('-'))
+            .and_then(|(name, _)| get(name));
+        if let Some(pair) = pair {
+            let input = fs::read_to_string(&path)?;
+            cases.push((pair, path, input));
+        }
+    }
+    cases.sort_by(|a, b| a.1.cmp(&b.1));
+    Ok(cases)
+}
+
+fn describe<T: Debug>(result: Result<T>) -> String {
+    match result {
+        Ok(value) => format!("{value:?}"),
+        Err(err) => format!("error: {err:#}"),
+    }
+}
+
+fn digit_only_calibration_lines(size: usize, rng: &mut Rng) -> String {
+    generate(1, size, rng)
+        .unwrap_or_default()
+        .to_ascii_uppercase()
+}
+
+fn describe_lines(input: &str, get_number: fn(&str) -> Option<u64>) -> String {
+    let numbers: Vec<Option<u64>> = input.lines().map(get_number).collect();
+    format!("{numbers:?}")
+}
+
+fn race_sheet(size: usize, rng: &mut Rng) -> String {
+    let max_time = (size as u64 * 10).min(MAX_RACE_TIME);
+    let races: Vec<(u64, u64)> = (0..rng.range(1..=MAX_RACES as u64))
+        .map(|_| {
+            let time = rng.range(0..=max_time);
+            (time, rng.range(0..=time * time / 4 + 1))
+        })
+        .collect();
+    let column = |value: u64| format!(" {value}");
+
+    format!(
+        "Time:{}\nDistance:{}\n",
+        races.iter().map(|race| column(race.0)).collect::<String>(),
+        races.iter().map(|race| column(race.1)).collect::<String>()
+    )
+}
+
+fn describe_margins(input: &str, get_margin: fn(&Race) -> Result<MarginOfError>) -> String {
+    match parse_races(input) {
+        Ok(races) => races
+            .iter()
+            .map(|race| describe(get_margin(race)))
+            .collect::<Vec<_>>()
+            .join("\n"),
+        Err(err) => format!("error: {err:#}"),
+    }
+}
+
+fn legacy_galaxies(input: &str) -> Result<Vec<(u128, u128)>> {
+    let image = legacy_parse(input.as_bytes())?;
+    Ok(image
+        .iter()
+        .enumerate()
+        .flat_map(|(y, row)| {
+            row.iter()
+                .enumerate()
+                .filter(|(_, is_galaxy)| **is_galaxy)
+                .map(move |(x, _)| (y as u128, x as u128))
+        })
+        .collect())
+}
+
+fn expanded_galaxies(input: &str) -> Result<Vec<(u128, u128)>> {
+    let image = parse_with_expansion(input.as_bytes(), 2)?;
+    Ok(image
+        .iter()
+        .flat_map(|(y, row)| {
+            row.iter()
+                .filter(|(_, is_galaxy)| *is_galaxy)
+                .map(move |(x, _)| (*y, *x))
+        })
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::Path;
+
+    use crate::{fixtures::DEFAULT_FIXTURES_DIR, rng::Rng};
+
+    use super::{cross_check, diverges, get, load_cases, Pair, PAIRS};
+
+    #[test]
+    fn pairs_agree_on_generated_inputs() {
+        for pair in PAIRS {
+            if let Some(divergence) = cross_check(pair, 100, 60, &mut Rng::new(pair.day.into())) {
+                panic!("{divergence:#?}");
+            }
+        }
+        assert!(get("day06-margin").is_some());
+        assert!(get("day07-ranks").is_none());
+    }
+
+    #[test]
+    fn recorded_cases_agree() {
+        for (pair, path, input) in load_cases(Path::new(DEFAULT_FIXTURES_DIR)).unwrap() {
+            assert_eq!(diverges(pair, &input), None, "{}", path.display());
+        }
+    }
+
+    #[test]
+    fn first_divergence_is_reported() {
+        let pair = Pair {
+            name: "length",
+            day: 0,
+            generate: |size, _| "x".repeat(size),
+            reference: |input| input.len().to_string(),
+            optimized: |input| input.len().min(5).to_string(),
+        };
+        let divergence = cross_check(&pair, 10, 10, &mut Rng::new(0)).unwrap();
+
+        assert_eq!(divergence.case, 6);
+        assert_eq!(divergence.input, "xxxxxx");
+        assert_eq!(
+            (divergence.reference, divergence.optimized),
+            ("6".into(), "5".into())
+        );
+    }
+
+    #[test]
+    fn panics_are_reported_as_divergences() {
+        let pair = Pair {
+            name: "slice",
+            day: 0,
+            generate: |size, _| "é".repeat(size),
+            reference: |input| input.to_string(),
+            optimized: |input| input[1..].to_string(),
+        };
+        let hook = std::panic::take_hook();
+        std::panic::set_hook(Box::new(|_| {}));
+        let divergence = cross_check(&pair, 10, 10, &mut Rng::new(0));
+        std::panic::set_hook(hook);
+
+        let divergence = divergence.unwrap();
+        assert_eq!(divergence.case, 0);
+        assert_eq!(divergence.reference, "é");
+        assert!(divergence.optimized.starts_with("panicked: "));
+        assert!(divergence.optimized.contains("not a char boundary"));
+    }
+}
@@ -0,0 +1,179 @@
+use std::{fmt::Display, io};
+
+pub type Result<T> = std::result::Result<T, AocError>;
+
+#[derive(Debug)]
+pub enum AocError {
+    Io(io::Error),
+    Parse {
+        line: Option<usize>,
+        span: Option<Span>,
+        message: String,
+    },
+    NoSolution(String),
+    Http(String),
+    Refused(String),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Span {
+    pub column: usize,
+    pub width: usize,
+    pub source: String,
+}
+
+impl Span {
+    fn render(&self, f: &mut std::fmt::Formatter<'_>, line: Option<usize>) -> std::fmt::Result {
+        let line_number = line.map(|line| line.to_string()).unwrap_or_default();
+        let gutter = " ".repeat(line_number.len());
+        let padding = " ".repeat(self.display_column() - 1);
+        let carets = "^".repeat(self.width.max(1));
+
+        writeln!(f)?;
+        writeln!(f, "{gutter} |")?;
+        writeln!(f, "{line_number} | {}", self.source)?;
+        write!(f, "{gutter} | {padding}{carets}")
+    }
+
+    fn display_column(&self) -> usize {
+        self.source
+            .get(..self.column)
+            .map_or(self.column, |before| before.chars().count())
+            + 1
+    }
+}
+
+impl AocError {
+    pub fn parse(message: impl Into<String>) -> Self {
+        AocError::Parse {
+            line: None,
+            span: None,
+            message: message.into(),
+        }
+    }
+
+    pub fn no_solution(message: impl Into<String>) -> Self {
+        AocError::NoSolution(message.into())
+    }
+
+    pub fn with_line(self, line: usize) -> Self {
+        match self {
+            AocError::Parse {
+                line: None,
+                span,
+                message,
+            } => AocError::Parse {
+                line: Some(line),
+                span,
+                message,
+            },
+            err => err,
+        }
+    }
+
+    pub fn with_span(self, column: usize, width: usize, source: &str) -> Self {
+        match self {
+            AocError::Parse {
+                line,
+                span: None,
+                message,
+            } => AocError::Parse {
+                line,
+                span: Some(Span {
+                    column,
+                    width,
+                    source: source.to_string(),
+                }),
+                message,
+            },
+            err => err,
+        }
+    }
+}
+
+impl Display for AocError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            AocError::Io(err) => write!(f, "{err}"),
+            AocError::Parse {
+                line,
+                span,
+                message,
+            } => {
+                match (line, span) {
+                    (Some(line), Some(span)) => {
+                        write!(f, "line {line}, column {}: ", span.display_column())?
+                    }
+                    (Some(line), None) => write!(f, "line {line}: ")?,
+                    (None, Some(span)) => write!(f, "column {}: ", span.display_column())?,
+                    (None, None) => (),
+                }
+                write!(f, "{message}")?;
+                match span {
+                    Some(span) if f.alternate() => span.render(f, *line),
+                    _ => Ok(()),
+                }
+            }
+            AocError::NoSolution(message) => write!(f, "no solution: {message}"),
+            AocError::Http(message) => write!(f, "request failed: {message}"),
+            AocError::Refused(message) => write!(f, "refusing to submit, {message}"),
+        }
+    }
+}
+
+impl std::error::Error for AocError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            AocError::Io(err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+impl From<io::Error> for AocError {
+    fn from(err: io::Error) -> Self {
+        AocError::Io(err)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::AocError;
+
+    #[test]
+    fn errors_are_displayed_with_line() {
+        let err = AocError::parse("unknown cube color `purple`");
+        assert_eq!(err.to_string(), "unknown cube color `purple`");
+
+        let err = err.with_line(3).with_line(7);
+        assert_eq!(err.to_string(), "line 3: unknown cube color `purple`");
+
+        let err = AocError::no_solution("no start tile").with_line(1);
+        assert_eq!(err.to_string(), "no solution: no start tile");
+    }
+
+    #[test]
+    fn errors_render_a_snippet() {
+        let err = AocError::parse("invalid card `X`")
+            .with_span(3, 1, "T55X5 684")
+            .with_line(12);
+
+        assert_eq!(err.to_string(), "line 12, column 4: invalid card `X`");
+        assert_eq!(
+            format!("{err:#}"),
+            "line 12, column 4: invalid card `X`
+   |
+12 | T55X5 684
+   |    ^"
+        );
+
+        let err = AocError::parse("invalid number `1x`").with_span(7, 2, "seeds: 1x 14");
+        assert_eq!(
+            format!("{err:#}"),
+            "column 8: invalid number `1x`
+ |
+ | seeds: 1x 14
+ |        ^^"
+        );
+    }
+}
@@ -0,0 +1,401 @@
+use std::{
+    fs, io,
+    path::{Path, PathBuf},
+};
+
+use crate::{
+    cli::Part,
+    error::{AocError, Result},
+    toml::Document,
+    utils::strip_tags,
+};
+
+pub const DEFAULT_FIXTURES_DIR: &str = "fixtures";
+pub const ANSWERS_FILE: &str = "answers.toml";
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Example {
+    pub input: String,
+    pub answers: Vec<(Part, String)>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Fixture {
+    pub day: u8,
+    pub name: String,
+    pub input: String,
+    pub answers: Vec<(Part, String)>,
+}
+
+pub fn extract_examples(html: &str) -> Vec<Example> {
+    let mut examples: Vec<Example> = vec![];
+    let articles = find_all(html, "<article", "</article>");
+    let articles = if articles.is_empty() {
+        vec![html]
+    } else {
+        articles
+    };
+
+    for (key, article) in articles.into_iter().enumerate() {
+        let mut sections = article.split("<pre><code>");
+        let mut answers = vec![];
+        let leading_answer = sections.next().and_then(last_answer);
+
+        for section in sections {
+            let Some((block, after)) = section.split_once("</code></pre>") else {
+                continue;
+            };
+            let input = decode_entities(&strip_tags(block));
+            let index = match examples.iter().position(|example| example.input == input) {
+                Some(index) => index,
+                None => {
+                    examples.push(Example {
+                        input,
+                        answers: vec![],
+                    });
+                    examples.len() - 1
+                }
+            };
+            if let Some(answer) = last_answer(after) {
+                answers.push((index, answer));
+            }
+        }
+        if answers.is_empty() {
+            if let (Some(answer), Some(index)) = (leading_answer, examples.len().checked_sub(1)) {
+                answers.push((index, answer));
+            }
+        }
+
+        let part = match key {
+            0 => Part::One,
+            1 => Part::Two,
+            _ => continue,
+        };
+        for (index, answer) in answers {
+            let example = &mut examples[index];
+            if example
+                .answers
+                .iter()
+                .all(|(answered, _)| *answered != part)
+            {
+                example.answers.push((part, answer));
+            }
+        }
+    }
+    examples
+}
+
+fn last_answer(html: &str) -> Option<String> {
+    find_all(html, "<code><em>", "</em></code>")
+        .last()
+        .map(|answer| decode_entities(&strip_tags(answer)))
+}
+
+pub fn write_fixtures(dir: &Path, day: u8, examples: &[Example]) -> Result<Vec<PathBuf>> {
+    let day_dir = dir.join(format!("day{day:02}"));
+    if day_dir.join(ANSWERS_FILE).exists() {
+        return Err(AocError::Io(io::Error::new(
+            io::ErrorKind::AlreadyExists,
+            format!("{} already exists", day_dir.join(ANSWERS_FILE).display()),
+        )));
+    }
+    fs::create_dir_all(&day_dir)?;
+
+    let mut written = vec![];
+    let mut answers = vec![];
+    for (key, example) in examples.iter().enumerate() {
+        let name = format!("example{}", key + 1);
+        let path = day_dir.join(format!("{name}.txt"));
+
+        fs::write(&path, &example.input)?;
+        written.push(path);
+        answers.push(format!("[{name}]"));
+        for (part, answer) in example.answers.iter() {
+            answers.push(format!("part{} = {}", part.number(), toml_value(answer)));
+        }
+        answers.push(String::new());
+    }
+
+    let path = day_dir.join(ANSWERS_FILE);
+    fs::write(&path, answers.join("\n").trim_end().to_string() + "\n")?;
+    written.push(path);
+    Ok(written)
+}
+
+pub fn load_fixtures(dir: &Path) -> Result<Vec<Fixture>> {
+    let mut fixtures = vec![];
+    let mut day_dirs: Vec<(u8, PathBuf)> = fs::read_dir(dir)?
+        .filter_map(|entry| entry.ok())
+        .filter_map(|entry| {
+            let day = entry
+                .file_name()
+                .to_str()?
+                .strip_prefix("day")?
+                .parse()
+                .ok()?;
+            Some((day, entry.path()))
+        })
+        .collect();
+    day_dirs.sort();
+
+    for (day, day_dir) in day_dirs {
+        fixtures.extend(load_day(&day_dir, day)?);
+    }
+    Ok(fixtures)
+}
+
+pub fn load_day_fixtures(dir: &Path, day: u8) -> Result<Vec<Fixture>> {
+    let day_dir = dir.join(format!("day{day:02}"));
+
+    if !day_dir.join(ANSWERS_FILE).exists() {
+        return Ok(vec![]);
+    }
+    load_day(&day_dir, day)
+}
+
+pub fn load_fixture(dir: &Path, day: u8, name: &str) -> Result<Fixture> {
+    let day_dir = dir.join(format!("day{day:02}"));
+
+    load_day(&day_dir, day)?
+        .into_iter()
+        .find(|fixture| fixture.name == name)
+        .ok_or_else(|| {
+            AocError::parse(format!(
+                "no `[{name}]` in {}",
+                day_dir.join(ANSWERS_FILE).display()
+            ))
+        })
+}
+
+fn load_day(day_dir: &Path, day: u8) -> Result<Vec<Fixture>> {
+    let answers_path = day_dir.join(ANSWERS_FILE);
+    let document = Document::parse(&fs::read_to_string(&answers_path)?)?;
+    let mut fixtures = vec![];
+
+    for (name, table) in document.tables() {
+        let mut answers = vec![];
+        for (key, value) in table {
+            let part = match key.as_str() {
+                "part1" => Part::One,
+                "part2" => Part::Two,
+                _ => {
+                    return Err(AocError::parse(format!(
+                        "unknown key `{key}` in `[{name}]` of {}",
+                        answers_path.display()
+                    )))
+                }
+            };
+            answers.push((part, value.to_string()));
+        }
+        fixtures.push(Fixture {
+            day,
+            name: name.to_string(),
+            input: fs::read_to_string(day_dir.join(format!("{name}.txt")))?,
+            answers,
+        });
+    }
+    Ok(fixtures)
+}
+
+#[cfg(test)]
+pub fn check_example(solution: &dyn crate::solution::Solution, part: Part, name: &str) {
+    let day = solution.day();
+    let fixture = load_fixture(Path::new(DEFAULT_FIXTURES_DIR), day, name)
+        .unwrap_or_else(|err| panic!("day {day} {name}: {err:#}"));
+    let expected = fixture
+        .answers
+        .iter()
+        .find_map(|(answer_part, answer)| (*answer_part == part).then_some(answer))
+        .unwrap_or_else(|| panic!("day {day} {name} has no answer for part {}", part.number()));
+
+    assert!(
+        check_answer(solution, part, &fixture, expected),
+        "day {day} part {} is not implemented",
+        part.number()
+    );
+}
+
+#[cfg(test)]
+fn check_answer(
+    solution: &dyn crate::solution::Solution,
+    part: Part,
+    fixture: &Fixture,
+    expected: &str,
+) -> bool {
+    let (day, name) = (fixture.day, &fixture.name);
+    let params = crate::params::Params::new(solution.params());
+    let answer = crate::runner::run_part(solution, part, &fixture.input, &params)
+        .unwrap_or_else(|err| panic!("day {day} part {} {name}: {err:#}", part.number()));
+    let Some(answer) = answer else {
+        return false;
+    };
+    assert_eq!(
+        answer.to_string(),
+        expected,
+        "day {day} part {} {name}",
+        part.number()
+    );
+    true
+}
+
+#[cfg(test)]
+macro_rules! examples {
+    ($solution:expr; $($test:ident: $part:ident, $name:literal;)+) => {
+        $(
+            #[test]
+            fn $test() {
+                $crate::fixtures::check_example(&$solution, $crate::cli::Part::$part, $name);
+            }
+        )+
+    };
+}
+
+#[cfg(test)]
+pub(crate) use examples;
+
+fn find_all<'a>(html: &'a str, open: &str, close: &str) -> Vec<&'a str> {
+    let mut found = vec![];
+    let mut rest = html;
+
+    while let Some(start) = rest.find(open) {
+        let after_open = &rest[start + open.len()..];
+        let content_start = if open.ends_with('>') {
+            0
+        } else {
+            after_open.find('>').map_or(0, |key| key + 1)
+        };
+        let Some(end) = after_open.find(close) else {
+            break;
+        };
+        if content_start <= end {
+            found.push(&after_open[content_start..end]);
+        }
+        rest = &after_open[end + close.len()..];
+    }
+    found
+}
+
+fn decode_entities(s: &str) -> String {
+    s.replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&#39;", "'")
+        .replace("&amp;", "&")
+}
+
+fn toml_value(answer: &str) -> String {
+    if answer.parse::<i128>().is_ok() {
+        answer.to_string()
+    } else {
+        format!("\"{}\"", answer.replace('\\', "\\\\").replace('"', "\\\""))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{fs, path::Path};
+
+    use crate::{cli::Part, solution};
+
+    use super::{
+        check_answer, extract_examples, load_fixtures, write_fixtures, DEFAULT_FIXTURES_DIR,
+    };
+
+    const PAGE: &str = r#"<main>
+<article class="day-desc"><h2>--- Day 7: Camel Cards ---</h2>
+<p>For example:</p>
+<pre><code>32T3K 765
+T55J5 684
+</code></pre>
+<p>So, the first step is <code><em>5</em></code>, then ... total winnings are <code><em>6440</em></code>.</p>
+</article>
+<article class="day-desc"><h2 id="part2">--- Part Two ---</h2>
+<p>Using the example, <code>J</code> cards are wild: <code><em>5905</em></code>.</p>
+</article>
+<article class="day-desc">
+<pre><code>a &lt; <em>b</em> &amp;&amp; c
+</code></pre>
+</article>
+</main>"#;
+
+    #[test]
+    fn examples_are_extracted_from_pages() {
+        let examples = extract_examples(PAGE);
+
+        assert_eq!(examples.len(), 2);
+        assert_eq!(examples[0].input, "32T3K 765\nT55J5 684\n");
+        assert_eq!(
+            examples[0].answers,
+            vec![
+                (Part::One, String::from("6440")),
+                (Part::Two, String::from("5905"))
+            ]
+        );
+        assert_eq!(examples[1].input, "a < b && c\n");
+        assert!(examples[1].answers.is_empty());
+    }
+
+    #[test]
+    fn answers_follow_their_examples() {
+        let examples = extract_examples(
+            r#"<article><p>For example:</p>
+<pre><code>RL
+AAA = (BBB, CCC)
+</code></pre>
+<p>Here, <code><em>2</em></code> steps are required.</p>
+<pre><code>LLR
+AAA = (BBB, BBB)
+</code></pre>
+<p>This takes <code><em>6</em></code> steps.</p>
+</article>
+<article><pre><code>LR
+</code></pre>
+<p>Ghosts take <code><em>6</em></code> steps.</p>
+</article>"#,
+        );
+
+        assert_eq!(examples.len(), 3);
+        assert_eq!(examples[0].answers, vec![(Part::One, String::from("2"))]);
+        assert_eq!(examples[1].answers, vec![(Part::One, String::from("6"))]);
+        assert_eq!(examples[2].answers, vec![(Part::Two, String::from("6"))]);
+    }
+
+    #[test]
+    fn fixtures_are_written_and_loaded() {
+        let dir = std::env::temp_dir().join(format!("aoc2023-fixtures-{}", std::process::id()));
+        let examples = extract_examples(PAGE);
+
+        write_fixtures(&dir, 7, &examples).unwrap();
+        assert!(write_fixtures(&dir, 7, &examples).is_err());
+        let fixtures = load_fixtures(&dir).unwrap();
+
+        assert_eq!(fixtures.len(), 2);
+        assert_eq!(fixtures[0].day, 7);
+        assert_eq!(fixtures[0].name, "example1");
+        assert_eq!(fixtures[0].input, examples[0].input);
+        assert_eq!(fixtures[0].answers, examples[0].answers);
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn fixtures_match_the_solutions() {
+        let mut checked = 0;
+
+        for fixture in load_fixtures(Path::new(DEFAULT_FIXTURES_DIR)).unwrap() {
+            let solution = solution::get(fixture.day)
+                .unwrap_or_else(|| panic!("day {} has no solution", fixture.day));
+            assert!(
+                !fixture.answers.is_empty(),
+                "day {} {} has no answers",
+                fixture.day,
+                fixture.name
+            );
+            for (part, expected) in fixture.answers.iter() {
+                if check_answer(solution, *part, &fixture, expected) {
+                    checked += 1;
+                }
+            }
+        }
+        assert!(checked > 0);
+    }
+}
@@ -0,0 +1,449 @@
+use std::collections::BTreeSet;
+
+use crate::rng::Rng;
+
+pub const DEFAULT_SIZE: usize = 100;
+pub const DEFAULT_SEED: u64 = 2023;
+
+const DIGIT_WORDS: [&str; 9] = [
+    "one", "two", "three", "four", "five", "six", "seven", "eight", "nine",
+];
+const COLORS: [&str; 3] = ["red", "green", "blue"];
+const SYMBOLS: &[u8] = b"*#+$/@=%&-";
+const CARDS: &[u8] = b"23456789TJQKA";
+const MAPS: [&str; 7] = [
+    "seed-to-soil",
+    "soil-to-fertilizer",
+    "fertilizer-to-water",
+    "water-to-light",
+    "light-to-temperature",
+    "temperature-to-humidity",
+    "humidity-to-location",
+];
+const MAX_RACES: u64 = 6;
+const MAX_SHEET_DIGITS: usize = 18;
+const MAX_NODES: usize = 26 * 26 * 26 - 2;
+
+const GENERATORS: &[fn(usize, &mut Rng) -> String] = &[
+    calibration_lines,
+    cube_games,
+    engine_schematic,
+    scratchcards,
+    almanac,
+    race_sheet,
+    camel_hands,
+    node_network,
+    oasis_histories,
+    pipe_loop,
+    galaxy_image,
+];
+
+pub fn generate(day: u8, size: usize, rng: &mut Rng) -> Option<String> {
+    generator(day).map(|generate| generate(size.max(1), rng))
+}
+
+pub fn supports(day: u8) -> bool {
+    generator(day).is_some()
+}
+
+fn generator(day: u8) -> Option<fn(usize, &mut Rng) -> String> {
+    GENERATORS.get(usize::from(day).checked_sub(1)?).copied()
+}
+
+fn calibration_lines(size: usize, rng: &mut Rng) -> String {
+    let mut input = String::new();
+
+    for _ in 0..size {
+        let mut tokens = vec![rng.range(1..=9).to_string()];
+        for _ in 0..rng.range(1..=6) {
+            tokens.push(match rng.below(3) {
+                0 => rng.range(1..=9).to_string(),
+                1 => rng.choose(&DIGIT_WORDS).to_string(),
+                _ => (0..rng.range(1..=5))
+                    .map(|_| (b'a' + rng.below(26) as u8) as char)
+                    .collect(),
+            });
+        }
+        rng.shuffle(&mut tokens);
+        input.push_str(&tokens.concat());
+        input.push('\n');
+    }
+    input
+}
+
+fn cube_games(size: usize, rng: &mut Rng) -> String {
+    let mut input = String::new();
+
+    for id in 1..=size {
+        let sets: Vec<String> = (0..rng.range(1..=6))
+            .map(|_| {
+                let mut colors = COLORS.to_vec();
+                rng.shuffle(&mut colors);
+                colors.truncate(rng.range(1..=3) as usize);
+                colors
+                    .iter()
+                    .map(|color| format!("{} {color}", rng.range(1..=20)))
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            })
+            .collect();
+        input.push_str(&format!("Game {id}: {}\n", sets.join("; ")));
+    }
+    input
+}
+
+fn engine_schematic(size: usize, rng: &mut Rng) -> String {
+    let width = size.max(3);
+    let mut input = String::new();
+
+    for _ in 0..size {
+        let mut row = String::with_capacity(width);
+        while row.len() < width {
+            match rng.below(8) {
+                0 => row.push(*rng.choose(SYMBOLS) as char),
+                1 | 2 => {
+                    let number = rng.range(1..=999).to_string();
+                    if row.len() + number.len() < width {
+                        row.push_str(&number);
+                    }
+                    row.push('.');
+                }
+                _ => row.push('.'),
+            }
+        }
+        input.push_str(&row[..width]);
+        input.push('\n');
+    }
+    input
+}
+
+fn scratchcards(size: usize, rng: &mut Rng) -> String {
+    let mut input = String::new();
+    let width = size.to_string().len();
+
+    for id in 1..=size {
+        let mut numbers: Vec<u64> = (1..=99).collect();
+        rng.shuffle(&mut numbers);
+        let wins = match rng.below(10) {
+            0 => rng.range(1..=10),
+            1..=3 => 1,
+            _ => 0,
+        }
+        .min((size - id) as u64);
+        let winning = &numbers[..10];
+        let mut owned: Vec<u64> = winning[..wins as usize].to_vec();
+        owned.extend_from_slice(&numbers[10..35 - wins as usize]);
+        rng.shuffle(&mut owned);
+
+        let format = |numbers: &[u64]| {
+            numbers
+                .iter()
+                .map(|number| format!("{number:>2}"))
+                .collect::<Vec<_>>()
+                .join(" ")
+        };
+        input.push_str(&format!(
+            "Card {id:>width$}: {} | {}\n",
+            format(winning),
+            format(&owned)
+        ));
+    }
+    input
+}
+
+fn almanac(size: usize, rng: &mut Rng) -> String {
+    let span = 1u64 << 32;
+    let seeds: Vec<String> = (0..size.max(2) & !1)
+        .map(|_| rng.below(span).to_string())
+        .collect();
+    let mut input = format!("seeds: {}\n", seeds.join(" "));
+
+    for name in MAPS {
+        let mut bounds: BTreeSet<u64> = (0..rng.range(1..=size as u64))
+            .map(|_| rng.below(span))
+            .collect();
+        bounds.insert(span);
+        let mut start = rng.below(span / 16);
+
+        input.push_str(&format!("\n{name} map:\n"));
+        let mut ranges = vec![];
+        for end in bounds {
+            if end > start {
+                ranges.push((rng.below(span - (end - start)), start, end - start));
+            }
+            start = end + rng.below(2) * rng.below(span / 1024);
+        }
+        rng.shuffle(&mut ranges);
+        for (destination, source, length) in ranges {
+            input.push_str(&format!("{destination} {source} {length}\n"));
+        }
+    }
+    input
+}
+
+fn race_sheet(size: usize, rng: &mut Rng) -> String {
+    let budget = (size + 1).min(MAX_SHEET_DIGITS);
+    let races = rng.range(1..=(budget as u64 / 2).clamp(1, MAX_RACES)) as usize;
+    let digits = (budget / races).min(MAX_SHEET_DIGITS / 2 / races + 1) as u32;
+    let races: Vec<(u64, u64)> = (0..races)
+        .map(|_| {
+            let time = rng.range(2 * 10u64.pow(digits - 1)..=10u64.pow(digits) - 1);
+            (time, rng.range(0..=10u64.pow(2 * digits - 2) - 1))
+        })
+        .collect();
+    let column = |value: u64| format!(" {value:>width$}", width = 2 * digits as usize);
+
+    format!(
+        "Time:    {}\nDistance:{}\n",
+        races.iter().map(|race| column(race.0)).collect::<String>(),
+        races.iter().map(|race| column(race.1)).collect::<String>()
+    )
+}
+
+fn camel_hands(size: usize, rng: &mut Rng) -> String {
+    let mut input = String::new();
+
+    for _ in 0..size {
+        let mut hand = [0u8; 5];
+        match rng.below(3) {
+            0 => hand.iter_mut().for_each(|card| *card = *rng.choose(CARDS)),
+            _ => {
+                let kinds = [*rng.choose(CARDS), *rng.choose(CARDS)];
+                for card in hand.iter_mut() {
+                    *card = if rng.chance(1, 5) {
+                        *rng.choose(CARDS)
+                    } else {
+                        *rng.choose(&kinds)
+                    };
+                }
+            }
+        }
+        input.push_str(&format!(
+            "{} {}\n",
+            String::from_utf8_lossy(&hand),
+            rng.range(1..=1000)
+        ));
+    }
+    input
+}
+
+fn node_network(size: usize, rng: &mut Rng) -> String {
+    let mut names: Vec<[u8; 3]> = (0..26 * 26 * 26)
+        .map(|n: usize| {
+            [
+                (n / 676) as u8 + b'A',
+                (n / 26 % 26) as u8 + b'A',
+                (n % 26) as u8 + b'A',
+            ]
+        })
+        .filter(|name| name != b"AAA" && name != b"ZZZ")
+        .collect();
+    rng.shuffle(&mut names);
+    names.truncate(size.min(MAX_NODES));
+    names.insert(0, *b"AAA");
+    names.push(*b"ZZZ");
+
+    let count = names.len();
+    let directions: String = (0..rng.range(2..=size as u64 + 1))
+        .map(|_| if rng.chance(1, 2) { 'L' } else { 'R' })
+        .collect();
+    let mut nodes: Vec<String> = names
+        .iter()
+        .enumerate()
+        .map(|(key, name)| {
+            let mut next = || {
+                let last = (key + 3).min(count - 1);
+                names[rng.range((key + 1).min(last) as u64..=last as u64) as usize]
+            };
+            let (left, right) = (next(), next());
+            format!(
+                "{} = ({}, {})",
+                String::from_utf8_lossy(name),
+                String::from_utf8_lossy(&left),
+                String::from_utf8_lossy(&right)
+            )
+        })
+        .collect();
+    rng.shuffle(&mut nodes);
+
+    format!("{directions}\n\n{}\n", nodes.join("\n"))
+}
+
+fn oasis_histories(size: usize, rng: &mut Rng) -> String {
+    let mut input = String::new();
+
+    for _ in 0..size {
+        let len = rng.range(5..=21) as usize;
+        let degree = rng.range(0..=(len as u64 - 2).min(6)) as usize;
+        let mut levels: Vec<Vec<i64>> = vec![vec![rng.signed(-10..=10); len]];
+        for _ in 0..degree {
+            let below = levels.last().unwrap();
+            let mut level = vec![rng.signed(-20..=20)];
+            for key in 1..len {
+                level.push(level[key - 1] + below[key - 1]);
+            }
+            levels.push(level);
+        }
+
+        let history = levels.last().unwrap();
+        let numbers: Vec<String> = history.iter().map(i64::to_string).collect();
+        input.push_str(&numbers.join(" "));
+        input.push('\n');
+    }
+    input
+}
+
+fn pipe_loop(size: usize, rng: &mut Rng) -> String {
+    let (width, height) = (size.max(2), size.max(4));
+    let half = height / 2;
+    let mut top = vec![rng.index(half)];
+    let mut bottom = vec![half + rng.index(height - half)];
+    for _ in 1..width {
+        let step = |value: usize, low: usize, high: usize, rng: &mut Rng| {
+            (value as i64 + rng.signed(-1..=1)).clamp(low as i64, high as i64 - 1) as usize
+        };
+        top.push(step(*top.last().unwrap(), 0, half, rng));
+        bottom.push(step(*bottom.last().unwrap(), half, height, rng));
+    }
+    top[width - 1] = top[width - 2];
+    bottom[0] = bottom[1];
+
+    let mut path = vec![(top[0], 0)];
+    let walk_to = |path: &mut Vec<(usize, usize)>, y: usize, x: usize| {
+        let (mut y0, mut x0) = *path.last().unwrap();
+        while (y0, x0) != (y, x) {
+            if y0 != y {
+                y0 = if y0 < y { y0 + 1 } else { y0 - 1 };
+            } else {
+                x0 = if x0 < x { x0 + 1 } else { x0 - 1 };
+            }
+            path.push((y0, x0));
+        }
+    };
+    for x in 1..width {
+        walk_to(&mut path, top[x - 1], x);
+        walk_to(&mut path, top[x], x);
+    }
+    walk_to(&mut path, bottom[width - 1], width - 1);
+    for x in (1..width).rev() {
+        walk_to(&mut path, bottom[x], x - 1);
+        walk_to(&mut path, bottom[x - 1], x - 1);
+    }
+    walk_to(&mut path, top[0], 0);
+    path.pop();
+
+    let mut grid: Vec<Vec<u8>> = (0..height)
+        .map(|_| (0..width).map(|_| *rng.choose(b"|-LJ7F....")).collect())
+        .collect();
+    let len = path.len();
+    for (key, &(y, x)) in path.iter().enumerate() {
+        let neighbours = [path[(key + len - 1) % len], path[(key + 1) % len]];
+        let north = neighbours.contains(&(y.wrapping_sub(1), x));
+        let south = neighbours.contains(&(y + 1, x));
+        let west = neighbours.contains(&(y, x.wrapping_sub(1)));
+        grid[y][x] = match (north, south, west) {
+            (true, true, _) => b'|',
+            (true, false, true) => b'J',
+            (true, false, false) => b'L',
+            (false, true, true) => b'7',
+            (false, true, false) => b'F',
+            (false, false, _) => b'-',
+        };
+    }
+
+    let (start_y, start_x) = path[rng.index(len)];
+    grid[start_y][start_x] = b'S';
+    for (y, x) in [
+        (start_y.wrapping_sub(1), start_x),
+        (start_y + 1, start_x),
+        (start_y, start_x.wrapping_sub(1)),
+        (start_y, start_x + 1),
+    ] {
+        if !path.contains(&(y, x)) {
+            if let Some(tile) = grid.get_mut(y).and_then(|row| row.get_mut(x)) {
+                *tile = b'.';
+            }
+        }
+    }
+
+    let mut input = String::new();
+    for row in grid {
+        input.push_str(&String::from_utf8_lossy(&row));
+        input.push('\n');
+    }
+    input
+}
+
+fn galaxy_image(size: usize, rng: &mut Rng) -> String {
+    let mut input = String::new();
+    let empty_rows: Vec<bool> = (0..size).map(|_| rng.chance(1, 10)).collect();
+    let empty_columns: Vec<bool> = (0..size).map(|_| rng.chance(1, 10)).collect();
+
+    for (y, empty_row) in empty_rows.iter().enumerate() {
+        for (x, empty_column) in empty_columns.iter().enumerate() {
+            let galaxy = (y, x) == (0, 0) || (!empty_row && !empty_column && rng.chance(1, 20));
+            input.push(if galaxy { '#' } else { '.' });
+        }
+        input.push('\n');
+    }
+    input
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{cli::Part, params::Params, rng::Rng, runner::run_part, solution};
+
+    use super::{generate, supports};
+
+    #[test]
+    fn generated_inputs_are_solved() {
+        for day in (1..=25).filter(|day| supports(*day)) {
+            let solution = solution::get(day).unwrap();
+            let params = Params::new(solution.params());
+
+            for (seed, size) in [(0, 1), (1, 2), (2, 7), (3, 40)] {
+                let input = generate(day, size, &mut Rng::new(seed)).unwrap();
+                for part in [Part::One, Part::Two] {
+                    if let Err(err) = run_part(solution, part, &input, &params) {
+                        panic!(
+                            "day {day} part {part:?}, seed {seed}, size {size}: {err:#}\n{input}"
+                        );
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn race_sheets_grow_with_the_size() {
+        let day06 = solution::get(6).unwrap();
+        let params = Params::new(day06.params());
+        let time_digits = |size| {
+            let input = generate(6, size, &mut Rng::new(size as u64)).unwrap();
+            for part in [Part::One, Part::Two] {
+                if let Err(err) = run_part(day06, part, &input, &params) {
+                    panic!("size {size}, part {part:?}: {err:#}\n{input}");
+                }
+            }
+            let times = input.lines().next().unwrap().split_whitespace().skip(1);
+            times.collect::<String>().len()
+        };
+
+        assert_eq!(time_digits(1), 2);
+        for size in [10, 100, 1000, 100_000] {
+            assert!(time_digits(size) >= 10, "size {size}");
+        }
+    }
+
+    #[test]
+    fn generators_are_seeded() {
+        assert!((1..=11).all(supports));
+        assert!(!supports(0) && !supports(12));
+        for day in 1..=11 {
+            let input = generate(day, 20, &mut Rng::new(5));
+            assert_eq!(input, generate(day, 20, &mut Rng::new(5)));
+            assert_ne!(input, generate(day, 20, &mut Rng::new(6)));
+        }
+        assert!(generate(12, 20, &mut Rng::new(5)).is_none());
+    }
+}
@@ -0,0 +1,278 @@
+use std::{
+    io::{Read, Write},
+    net::TcpStream,
+    process::{Command, Stdio},
+    time::Duration,
+};
+
+use crate::error::{AocError, Result};
+
+const TIMEOUT: Duration = Duration::from_secs(30);
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Response {
+    pub status: u16,
+    pub body: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Request<'a> {
+    pub method: &'a str,
+    pub url: &'a str,
+    pub headers: Vec<(&'a str, String)>,
+    pub body: Option<String>,
+}
+
+impl<'a> Request<'a> {
+    pub fn get(url: &'a str) -> Self {
+        Request {
+            method: "GET",
+            url,
+            headers: vec![],
+            body: None,
+        }
+    }
+
+    pub fn post_form(url: &'a str, body: String) -> Self {
+        Request {
+            method: "POST",
+            url,
+            headers: vec![(
+                "Content-Type",
+                String::from("application/x-www-form-urlencoded"),
+            )],
+            body: Some(body),
+        }
+    }
+
+    pub fn header(mut self, name: &'a str, value: impl Into<String>) -> Self {
+        self.headers.push((name, value.into()));
+        self
+    }
+
+    pub fn send(&self) -> Result<Response> {
+        if let Some(rest) = self.url.strip_prefix("http://") {
+            self.send_plain(rest)
+        } else if self.url.starts_with("https://") {
+            self.send_with_curl()
+        } else {
+            Err(AocError::Http(format!("unsupported url `{}`", self.url)))
+        }
+    }
+
+    fn send_plain(&self, rest: &str) -> Result<Response> {
+        let (authority, path) = match rest.find('/') {
+            Some(key) => (&rest[..key], &rest[key..]),
+            None => (rest, "/"),
+        };
+        let address = if authority.contains(':') {
+            authority.to_string()
+        } else {
+            format!("{authority}:80")
+        };
+
+        let mut request = format!(
+            "{} {path} HTTP/1.1\r\nHost: {authority}\r\nConnection: close\r\n",
+            self.method
+        );
+        for (name, value) in self.headers.iter() {
+            request.push_str(&format!("{name}: {value}\r\n"));
+        }
+        let body = self.body.as_deref().unwrap_or_default();
+        if self.body.is_some() {
+            request.push_str(&format!("Content-Length: {}\r\n", body.len()));
+        }
+        request.push_str("\r\n");
+        request.push_str(body);
+
+        let mut stream = TcpStream::connect(address)?;
+        stream.set_read_timeout(Some(TIMEOUT))?;
+        stream.write_all(request.as_bytes())?;
+        let mut raw = vec![];
+        stream.read_to_end(&mut raw)?;
+        parse_response(&raw)
+    }
+
+    fn send_with_curl(&self) -> Result<Response> {
+        let mut command = Command::new("curl");
+        command
+            .args(["--silent", "--show-error", "--request", self.method])
+            .args(["--header", "@-", "--write-out", "\n%{http_code}"])
+            .arg(self.url)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped());
+        if let Some(body) = self.body.as_deref() {
+            command.args(["--data-raw", body]);
+        }
+
+        let mut child = command
+            .spawn()
+            .map_err(|err| AocError::Http(format!("could not run `curl`: {err}")))?;
+        if let Some(mut stdin) = child.stdin.take() {
+            for (name, value) in self.headers.iter() {
+                writeln!(stdin, "{name}: {value}")?;
+            }
+        }
+        let output = child.wait_with_output()?;
+        if !output.status.success() {
+            return Err(AocError::Http(
+                String::from_utf8_lossy(&output.stderr).trim().to_string(),
+            ));
+        }
+
+        let stdout = String::from_utf8_lossy(&output.stdout);
+        let (body, status) = stdout
+            .rsplit_once('\n')
+            .ok_or_else(|| AocError::Http(String::from("malformed `curl` output")))?;
+        Ok(Response {
+            status: status
+                .trim()
+                .parse()
+                .map_err(|_| AocError::Http(format!("invalid status code `{status}`")))?,
+            body: body.to_string(),
+        })
+    }
+}
+
+pub fn parse_response(raw: &[u8]) -> Result<Response> {
+    let malformed = |message: &str| AocError::Http(format!("malformed response: {message}"));
+
+    let header_end = raw
+        .windows(4)
+        .position(|window| window == b"\r\n\r\n")
+        .ok_or_else(|| malformed("missing end of headers"))?;
+    let head = String::from_utf8_lossy(&raw[..header_end]);
+    let body = &raw[header_end + 4..];
+
+    let mut lines = head.split("\r\n");
+    let status = lines
+        .next()
+        .and_then(|line| line.split_whitespace().nth(1))
+        .and_then(|status| status.parse().ok())
+        .ok_or_else(|| malformed("invalid status line"))?;
+    let chunked = lines.any(|line| {
+        line.split_once(':').is_some_and(|(name, value)| {
+            name.trim().eq_ignore_ascii_case("transfer-encoding")
+                && value.trim().eq_ignore_ascii_case("chunked")
+        })
+    });
+
+    let body = if chunked {
+        decode_chunked(body).ok_or_else(|| malformed("invalid chunked body"))?
+    } else {
+        body.to_vec()
+    };
+    Ok(Response {
+        status,
+        body: String::from_utf8_lossy(&body).to_string(),
+    })
+}
+
+fn decode_chunked(mut raw: &[u8]) -> Option<Vec<u8>> {
+    let mut body = vec![];
+
+    loop {
+        let line_end = raw.windows(2).position(|window| window == b"\r\n")?;
+        let size_str = std::str::from_utf8(&raw[..line_end]).ok()?;
+        let size_str = size_str.split(';').next()?.trim();
+        let size = usize::from_str_radix(size_str, 16).ok()?;
+        raw = &raw[line_end + 2..];
+
+        if size == 0 {
+            return Some(body);
+        }
+        body.extend_from_slice(raw.get(..size)?);
+        raw = raw.get(size + 2..)?;
+    }
+}
+
+#[cfg(test)]
+pub mod mock {
+    use std::{
+        io::{Read, Write},
+        net::TcpListener,
+        sync::mpsc::{self, Receiver},
+        thread,
+    };
+
+    pub fn serve(responses: Vec<String>) -> (String, Receiver<String>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        let (sender, receiver) = mpsc::channel();
+
+        thread::spawn(move || {
+            for response in responses {
+                let (mut stream, _) = listener.accept().unwrap();
+                let mut request = vec![];
+                let mut buffer = [0; 4096];
+
+                while let Ok(read) = stream.read(&mut buffer) {
+                    request.extend_from_slice(&buffer[..read]);
+                    if read == 0 || is_complete(&request) {
+                        break;
+                    }
+                }
+                sender
+                    .send(String::from_utf8_lossy(&request).to_string())
+                    .unwrap();
+                stream.write_all(response.as_bytes()).unwrap();
+            }
+        });
+        (url, receiver)
+    }
+
+    pub fn response(status: u16, body: &str) -> String {
+        format!(
+            "HTTP/1.1 {status} Mock\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+            body.len()
+        )
+    }
+
+    fn is_complete(request: &[u8]) -> bool {
+        let Some(header_end) = request.windows(4).position(|window| window == b"\r\n\r\n") else {
+            return false;
+        };
+        let head = String::from_utf8_lossy(&request[..header_end]).to_ascii_lowercase();
+        let length = head
+            .lines()
+            .find_map(|line| line.strip_prefix("content-length:"))
+            .and_then(|length| length.trim().parse().ok())
+            .unwrap_or(0);
+        request.len() >= header_end + 4 + length
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{mock, parse_response, Request};
+
+    #[test]
+    fn requests_are_sent_over_plain_http() {
+        let (url, requests) = mock::serve(vec![mock::response(200, "ok")]);
+        let response = Request::post_form(&format!("{url}/submit"), String::from("level=1"))
+            .header("Cookie", "session=abc")
+            .send()
+            .unwrap();
+        let request = requests.recv().unwrap();
+
+        assert_eq!(response.status, 200);
+        assert_eq!(response.body, "ok");
+        assert!(request.starts_with("POST /submit HTTP/1.1\r\n"));
+        assert!(request.contains("Cookie: session=abc\r\n"));
+        assert!(request.ends_with("\r\n\r\nlevel=1"));
+    }
+
+    #[test]
+    fn chunked_responses_are_decoded() {
+        let response = parse_response(
+            b"HTTP/1.1 404 Not Found\r\nTransfer-Encoding: chunked\r\n\r\n4\r\nWiki\r\n5;x=1\r\npedia\r\n0\r\n\r\n",
+        )
+        .unwrap();
+
+        assert_eq!(response.status, 404);
+        assert_eq!(response.body, "Wikipedia");
+        assert!(parse_response(b"HTTP/1.1 200 OK\r\n").is_err());
+        assert!(Request::get("ftp://example.com").send().is_err());
+    }
+}
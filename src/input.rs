@@ -0,0 +1,84 @@
+use std::{
+    fs,
+    io::{self, Read},
+    path::{Path, PathBuf},
+};
+
+pub const INPUT_DIR_VAR: &str = "AOC_INPUT_DIR";
+pub const DEFAULT_INPUT_DIR: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/src");
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum InputSource {
+    Dir(PathBuf),
+    File(PathBuf),
+    Stdin,
+}
+
+impl InputSource {
+    pub fn from_arg(arg: &str) -> Self {
+        match arg {
+            "-" => InputSource::Stdin,
+            path => InputSource::File(PathBuf::from(path)),
+        }
+    }
+
+    pub fn is_single(&self) -> bool {
+        !matches!(self, InputSource::Dir(_))
+    }
+
+    pub fn describe(&self, day: u8) -> String {
+        match self {
+            InputSource::Dir(dir) => day_input_path(dir, day).display().to_string(),
+            InputSource::File(path) => path.display().to_string(),
+            InputSource::Stdin => String::from("<stdin>"),
+        }
+    }
+
+    pub fn read(&self, day: u8) -> io::Result<String> {
+        match self {
+            InputSource::Dir(dir) => fs::read_to_string(day_input_path(dir, day)),
+            InputSource::File(path) => fs::read_to_string(path),
+            InputSource::Stdin => {
+                let mut input = String::new();
+                io::stdin().read_to_string(&mut input)?;
+                Ok(input)
+            }
+        }
+    }
+}
+
+pub fn day_input_path(dir: &Path, day: u8) -> PathBuf {
+    dir.join(format!("day{day:02}")).join("input.txt")
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::{Path, PathBuf};
+
+    use super::{day_input_path, InputSource};
+
+    #[test]
+    fn input_paths_are_resolved() {
+        let dir = InputSource::Dir(PathBuf::from("inputs"));
+
+        assert_eq!(
+            day_input_path(Path::new("inputs"), 7),
+            Path::new("inputs/day07/input.txt")
+        );
+        assert_eq!(dir.describe(11), "inputs/day11/input.txt");
+        assert_eq!(InputSource::from_arg("-"), InputSource::Stdin);
+        assert_eq!(
+            InputSource::from_arg("my.txt"),
+            InputSource::File(PathBuf::from("my.txt"))
+        );
+        assert!(!dir.is_single());
+    }
+
+    #[test]
+    fn day_inputs_are_read_from_dir() {
+        let dir = InputSource::Dir(PathBuf::from(super::DEFAULT_INPUT_DIR));
+
+        assert!(dir.read(1).is_ok_and(|input| !input.is_empty()));
+        assert!(dir.read(25).is_err());
+    }
+}
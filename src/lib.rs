@@ -0,0 +1,37 @@
+pub mod alloc;
+pub mod answers;
+pub mod bench;
+pub mod cli;
+pub mod client;
+pub mod config;
+pub mod day01;
+pub mod day02;
+pub mod day03;
+pub mod day04;
+pub mod day05;
+pub mod day06;
+pub mod day07;
+pub mod day08;
+pub mod day09;
+pub mod day10;
+pub mod day11;
+pub mod differential;
+pub mod error;
+pub mod fixtures;
+pub mod generate;
+pub mod http;
+pub mod input;
+pub mod params;
+pub mod pool;
+pub mod report;
+pub mod rng;
+pub mod robustness;
+pub mod runner;
+pub mod scaffold;
+pub mod shrink;
+pub mod solution;
+pub mod submit;
+pub mod toml;
+pub mod trace;
+pub mod utils;
+pub mod watch;
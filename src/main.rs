@@ -1,34 +1,44 @@
-mod day01;
-mod day02;
-mod day03;
-mod day04;
-mod day05;
-mod day06;
-mod day07;
-mod day08;
-mod day09;
-mod day10;
-mod day11;
-mod utils;
+use std::process::ExitCode;
 
-fn main() {
-    day01::part_one();
-    day01::part_two();
-    day02::part_one();
-    day02::part_two();
-    day03::part_one();
-    day03::part_two();
-    day04::part_one();
-    day04::part_two();
-    day05::part_one();
-    day06::part_one();
-    day06::part_two();
-    day07::part_one();
-    day07::part_two();
-    day08::part_one();
-    day09::part_one();
-    day09::part_two();
-    day10::part_one();
-    day11::part_one();
-    day11::part_two();
+use aoc2023::{
+    cli::{self, Command},
+    config::{self, Config},
+    runner,
+};
+
+fn main() -> ExitCode {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    let path = config::config_path(cli::config_flag(&args));
+    let config = match Config::load(&path)
+        .and_then(|config| config.with_env(|name| std::env::var(name).ok()))
+    {
+        Ok(config) => config,
+        Err(err) => {
+            eprintln!("error: could not load {}: {err:#}", path.display());
+            return ExitCode::FAILURE;
+        }
+    };
+
+    match cli::parse_args(args, &config) {
+        Ok(Command::Run(args)) => runner::run(args, &config),
+        Ok(Command::Bench(args)) => runner::bench(args, &config),
+        Ok(Command::Verify(args)) => runner::verify(args, &config),
+        Ok(Command::New(args)) => runner::new_day(args),
+        Ok(Command::Fetch(args)) => runner::fetch(args, &config),
+        Ok(Command::Submit(args)) => runner::submit(args, &config),
+        Ok(Command::Examples(args)) => runner::examples(args),
+        Ok(Command::Watch(args)) => runner::watch(args, &config),
+        Ok(Command::Generate(args)) => runner::generate(args),
+        Ok(Command::Fuzz(args)) => runner::fuzz(args, &config),
+        Ok(Command::Compare(args)) => runner::compare(args),
+        Ok(Command::Shrink(args)) => runner::shrink(args, &config),
+        Ok(Command::Help) => {
+            println!("{}", cli::USAGE);
+            ExitCode::SUCCESS
+        }
+        Err(message) => {
+            eprintln!("error: {message}\n\n{}", cli::USAGE);
+            ExitCode::from(2)
+        }
+    }
 }
@@ -0,0 +1,122 @@
+use crate::{
+    error::{AocError, Result},
+    toml::{Table, Value},
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Param {
+    pub name: &'static str,
+    pub default: &'static str,
+}
+
+impl Param {
+    pub const fn new(name: &'static str, default: &'static str) -> Self {
+        Param { name, default }
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Params {
+    declared: &'static [Param],
+    values: Table,
+}
+
+impl Params {
+    pub fn new(declared: &'static [Param]) -> Self {
+        Params {
+            declared,
+            values: declared
+                .iter()
+                .map(|param| (param.name.to_string(), Value::String(param.default.into())))
+                .collect(),
+        }
+    }
+
+    pub fn declares(&self, key: &str) -> bool {
+        self.declared.iter().any(|param| param.name == key)
+    }
+
+    pub fn set(&mut self, key: &str, value: Value) -> Result<()> {
+        if !self.declares(key) {
+            return Err(AocError::parse(format!(
+                "unknown parameter `{key}`, expected {}",
+                describe(self.declared)
+            )));
+        }
+        self.values.insert(key.to_string(), value);
+        Ok(())
+    }
+
+    pub fn get_u64(&self, key: &str) -> Result<u64> {
+        match self.get(key)? {
+            Value::Integer(value) => u64::try_from(*value).map_err(|_| {
+                AocError::parse(format!(
+                    "parameter `{key}` must not be negative, got {value}"
+                ))
+            }),
+            Value::String(value) => value.parse().map_err(|_| {
+                AocError::parse(format!("parameter `{key}` must be a number, got `{value}`"))
+            }),
+            value => Err(AocError::parse(format!(
+                "parameter `{key}` must be a number, got `{value}`"
+            ))),
+        }
+    }
+
+    pub fn get_str(&self, key: &str) -> Result<&str> {
+        match self.get(key)? {
+            Value::String(value) => Ok(value),
+            value => Err(AocError::parse(format!(
+                "parameter `{key}` must be a string, got `{value}`"
+            ))),
+        }
+    }
+
+    fn get(&self, key: &str) -> Result<&Value> {
+        self.values
+            .get(key)
+            .ok_or_else(|| AocError::parse(format!("parameter `{key}` is not declared")))
+    }
+}
+
+pub fn describe(declared: &[Param]) -> String {
+    if declared.is_empty() {
+        return String::from("none");
+    }
+    declared
+        .iter()
+        .map(|param| format!("`{}` (default {})", param.name, param.default))
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::toml::Value;
+
+    use super::{Param, Params};
+
+    const DECLARED: &[Param] = &[Param::new("expansion", "2"), Param::new("start", "AAA")];
+
+    #[test]
+    fn declared_parameters_can_be_overridden() {
+        let mut params = Params::new(DECLARED);
+
+        assert_eq!(params.get_u64("expansion").unwrap(), 2);
+        assert_eq!(params.get_str("start").unwrap(), "AAA");
+
+        params.set("expansion", Value::Integer(10)).unwrap();
+        assert_eq!(params.get_u64("expansion").unwrap(), 10);
+        params
+            .set("expansion", Value::String(String::from("20")))
+            .unwrap();
+        assert_eq!(params.get_u64("expansion").unwrap(), 20);
+
+        assert!(params.set("red", Value::Integer(1)).is_err());
+        params.set("expansion", Value::Integer(-1)).unwrap();
+        assert!(params.get_u64("expansion").is_err());
+        params.set("start", Value::Boolean(true)).unwrap();
+        assert!(params.get_str("start").is_err());
+        assert!(Params::default().get_u64("expansion").is_err());
+    }
+}
@@ -0,0 +1,62 @@
+use std::{
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Mutex,
+    },
+    thread,
+};
+
+pub fn map_parallel<T, R, F>(items: &[T], jobs: usize, f: F) -> Vec<R>
+where
+    T: Sync,
+    R: Send,
+    F: Fn(&T) -> R + Sync,
+{
+    let workers = jobs.clamp(1, items.len().max(1));
+    if workers == 1 {
+        return items.iter().map(f).collect();
+    }
+
+    let next = AtomicUsize::new(0);
+    let results: Mutex<Vec<Option<R>>> = Mutex::new(items.iter().map(|_| None).collect());
+
+    thread::scope(|scope| {
+        for _ in 0..workers {
+            scope.spawn(|| loop {
+                let key = next.fetch_add(1, Ordering::Relaxed);
+                let Some(item) = items.get(key) else {
+                    break;
+                };
+                let result = f(item);
+                results.lock().unwrap()[key] = Some(result);
+            });
+        }
+    });
+
+    results
+        .into_inner()
+        .unwrap()
+        .into_iter()
+        .map(|result| result.expect("every item is processed by a worker"))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{thread, time::Duration};
+
+    use super::map_parallel;
+
+    #[test]
+    fn results_keep_the_input_order() {
+        let items: Vec<u64> = (1..=20).collect();
+        let squares = map_parallel(&items, 4, |n| {
+            thread::sleep(Duration::from_millis(20 - n));
+            n * n
+        });
+
+        assert_eq!(squares, items.iter().map(|n| n * n).collect::<Vec<u64>>());
+        assert_eq!(map_parallel(&items, 1, |n| n + 1)[0], 2);
+        assert!(map_parallel(&[] as &[u64], 8, |n| *n).is_empty());
+    }
+}
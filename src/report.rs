@@ -0,0 +1,263 @@
+use std::time::Duration;
+
+use crate::{alloc::AllocStats, cli::Part, solution::Answer};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Format {
+    Text,
+    Json,
+    Csv,
+}
+
+impl Format {
+    pub fn from_arg(arg: &str) -> Result<Self, String> {
+        match arg {
+            "text" => Ok(Format::Text),
+            "json" => Ok(Format::Json),
+            "csv" => Ok(Format::Csv),
+            _ => Err(format!(
+                "invalid format `{arg}`, expected text, json or csv"
+            )),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Status {
+    Ok(Answer),
+    Unimplemented,
+    Error(String),
+}
+
+impl Status {
+    fn name(&self) -> &'static str {
+        match self {
+            Status::Ok(_) => "ok",
+            Status::Unimplemented => "unimplemented",
+            Status::Error(_) => "error",
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Record {
+    pub day: u8,
+    pub title: &'static str,
+    pub part: Part,
+    pub status: Status,
+    pub duration: Duration,
+    pub input: String,
+    pub allocs: Option<AllocStats>,
+}
+
+pub fn print_text(records: &[Record], quiet: bool) {
+    let mut last_day = None;
+
+    for record in records {
+        let (day, part) = (record.day, record.part.number());
+
+        if !quiet && last_day != Some(day) {
+            println!("Day {day}: {}", record.title);
+            last_day = Some(day);
+        }
+        match (&record.status, quiet) {
+            (Status::Ok(answer), true) => println!("{answer}"),
+            (Status::Ok(answer), false) => match &record.allocs {
+                Some(allocs) => println!("    part {part}: {answer} ({allocs})"),
+                None => println!("    part {part}: {answer}"),
+            },
+            (Status::Unimplemented, true) => {
+                eprintln!("Day {day} part {part} is not implemented yet")
+            }
+            (Status::Unimplemented, false) => println!("    part {part}: not implemented yet"),
+            (Status::Error(err), _) => eprintln!("Day {day} part {part} failed: {err}"),
+        }
+    }
+}
+
+pub fn render_json(records: &[Record]) -> String {
+    let objects: Vec<String> = records
+        .iter()
+        .map(|record| {
+            let answer = match &record.status {
+                Status::Ok(Answer::Text(text)) => json_string(text),
+                Status::Ok(answer) => answer.to_string(),
+                _ => String::from("null"),
+            };
+            let error = match &record.status {
+                Status::Error(err) => json_string(err),
+                _ => String::from("null"),
+            };
+
+            let allocs = match &record.allocs {
+                Some(allocs) => format!(
+                    ", \"allocations\": {}, \"allocated_bytes\": {}, \"peak_bytes\": {}",
+                    allocs.allocations, allocs.bytes, allocs.peak
+                ),
+                None => String::new(),
+            };
+
+            format!(
+                "  {{\"day\": {}, \"part\": {}, \"status\": \"{}\", \"answer\": {answer}, \"duration_ns\": {}, \"input\": {}, \"error\": {error}{allocs}}}",
+                record.day,
+                record.part.number(),
+                record.status.name(),
+                record.duration.as_nanos(),
+                json_string(&record.input)
+            )
+        })
+        .collect();
+
+    if objects.is_empty() {
+        String::from("[]")
+    } else {
+        format!("[\n{}\n]", objects.join(",\n"))
+    }
+}
+
+pub fn render_csv(records: &[Record]) -> String {
+    let mut csv = String::from("day,part,status,answer,duration_ns,input,error");
+    let with_allocs = records.iter().any(|record| record.allocs.is_some());
+
+    if with_allocs {
+        csv.push_str(",allocations,allocated_bytes,peak_bytes");
+    }
+
+    for record in records {
+        let (answer, error) = match &record.status {
+            Status::Ok(answer) => (answer.to_string(), String::new()),
+            Status::Unimplemented => (String::new(), String::new()),
+            Status::Error(err) => (String::new(), err.clone()),
+        };
+
+        csv.push_str(&format!(
+            "\n{},{},{},{},{},{},{}",
+            record.day,
+            record.part.number(),
+            record.status.name(),
+            csv_field(&answer),
+            record.duration.as_nanos(),
+            csv_field(&record.input),
+            csv_field(&error)
+        ));
+        match &record.allocs {
+            Some(allocs) if with_allocs => csv.push_str(&format!(
+                ",{},{},{}",
+                allocs.allocations, allocs.bytes, allocs.peak
+            )),
+            None if with_allocs => csv.push_str(",,,"),
+            _ => (),
+        }
+    }
+    csv
+}
+
+fn json_string(s: &str) -> String {
+    let mut escaped = String::from('"');
+
+    for c in s.chars() {
+        match c {
+            '"' => escaped.push_str("\\\""),
+            '\\' => escaped.push_str("\\\\"),
+            '\n' => escaped.push_str("\\n"),
+            '\r' => escaped.push_str("\\r"),
+            '\t' => escaped.push_str("\\t"),
+            c if c.is_control() => escaped.push_str(&format!("\\u{:04x}", c as u32)),
+            c => escaped.push(c),
+        }
+    }
+    escaped.push('"');
+    escaped
+}
+
+fn csv_field(s: &str) -> String {
+    if s.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", s.replace('"', "\"\""))
+    } else {
+        s.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use crate::{alloc::AllocStats, cli::Part, solution::Answer};
+
+    use super::{render_csv, render_json, Record, Status};
+
+    fn records() -> Vec<Record> {
+        vec![
+            Record {
+                day: 5,
+                title: "If You Give A Seed A Fertilizer",
+                part: Part::One,
+                status: Status::Ok(Answer::Integer(35)),
+                duration: Duration::from_nanos(1200),
+                input: String::from("src/day05/input.txt"),
+                allocs: None,
+            },
+            Record {
+                day: 5,
+                title: "If You Give A Seed A Fertilizer",
+                part: Part::Two,
+                status: Status::Unimplemented,
+                duration: Duration::ZERO,
+                input: String::from("src/day05/input.txt"),
+                allocs: None,
+            },
+            Record {
+                day: 7,
+                title: "Camel Cards",
+                part: Part::One,
+                status: Status::Error(String::from("line 2, column 4: invalid card `X`")),
+                duration: Duration::from_nanos(80),
+                input: String::from("<stdin>"),
+                allocs: None,
+            },
+        ]
+    }
+
+    #[test]
+    fn records_are_rendered_as_json() {
+        assert_eq!(
+            render_json(&records()),
+            r#"[
+  {"day": 5, "part": 1, "status": "ok", "answer": 35, "duration_ns": 1200, "input": "src/day05/input.txt", "error": null},
+  {"day": 5, "part": 2, "status": "unimplemented", "answer": null, "duration_ns": 0, "input": "src/day05/input.txt", "error": null},
+  {"day": 7, "part": 1, "status": "error", "answer": null, "duration_ns": 80, "input": "<stdin>", "error": "line 2, column 4: invalid card `X`"}
+]"#
+        );
+        assert_eq!(render_json(&[]), "[]");
+    }
+
+    #[test]
+    fn records_are_rendered_as_csv() {
+        assert_eq!(
+            render_csv(&records()),
+            "day,part,status,answer,duration_ns,input,error
+5,1,ok,35,1200,src/day05/input.txt,
+5,2,unimplemented,,0,src/day05/input.txt,
+7,1,error,,80,<stdin>,\"line 2, column 4: invalid card `X`\""
+        );
+    }
+
+    #[test]
+    fn allocation_stats_are_rendered_when_present() {
+        let mut records = records();
+        records[0].allocs = Some(AllocStats {
+            allocations: 3,
+            bytes: 4096,
+            peak: 2048,
+        });
+
+        assert!(render_json(&records).contains(
+            r#""error": null, "allocations": 3, "allocated_bytes": 4096, "peak_bytes": 2048},"#
+        ));
+        let csv = render_csv(&records);
+        let lines: Vec<&str> = csv.lines().collect();
+        assert!(lines[0].ends_with(",error,allocations,allocated_bytes,peak_bytes"));
+        assert!(lines[1].ends_with(",src/day05/input.txt,,3,4096,2048"));
+        assert!(lines[2].ends_with(",unimplemented,,0,src/day05/input.txt,,,,"));
+    }
+}
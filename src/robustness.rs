@@ -0,0 +1,346 @@
+use std::{
+    any::Any,
+    fs,
+    panic::{self, AssertUnwindSafe},
+    path::{Path, PathBuf},
+};
+
+use crate::{
+    cli::Part, error::Result, params::Params, rng::Rng, runner::run_part, solution::Solution,
+};
+
+pub const REGRESSIONS_DIR: &str = "regressions";
+pub const DEFAULT_MUTATIONS: u32 = 1000;
+
+const MAX_MUTATIONS_PER_INPUT: u64 = 4;
+const SPECIAL_BYTES: &[u8] = b" \n\r\t0123456789-:,|=()#.S\xff";
+const MAX_DIGIT_RUN: u64 = 40;
+const MAX_REPEATS: u64 = 64;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Crash {
+    pub day: u8,
+    pub part: Part,
+    pub input: String,
+    pub message: String,
+}
+
+pub fn mutate(input: &[u8], rng: &mut Rng) -> Vec<u8> {
+    let mut bytes = input.to_vec();
+
+    for _ in 0..rng.range(1..=MAX_MUTATIONS_PER_INPUT) {
+        let len = bytes.len();
+        let at = rng.index(len + 1);
+        let byte = if len > 0 && rng.chance(1, 2) {
+            bytes[rng.index(len)]
+        } else {
+            *rng.choose(SPECIAL_BYTES)
+        };
+
+        match (rng.below(8), len) {
+            (_, 0) | (0, _) => bytes.insert(at, byte),
+            (1, _) => {
+                bytes.remove(at.min(len - 1));
+            }
+            (2, _) => bytes[at.min(len - 1)] = byte,
+            (3, _) => {
+                let end = (at + 1 + rng.index(16)).min(len);
+                bytes.drain(at.min(len - 1)..end);
+            }
+            (4, _) => {
+                let end = (at + 1 + rng.index(16)).min(len);
+                let chunk = bytes[at.min(len - 1)..end].to_vec();
+                bytes.splice(at..at, chunk);
+            }
+            (5, _) => {
+                let digits: Vec<u8> = (0..rng.range(10..=MAX_DIGIT_RUN))
+                    .map(|_| b'0' + rng.below(10) as u8)
+                    .collect();
+                bytes.splice(at..at, digits);
+            }
+            (6, _) => {
+                let start = at.min(len - 1);
+                let end = (start + 1 + rng.index(8)).min(len);
+                let chunk = bytes[start..end].repeat(rng.range(2..=MAX_REPEATS) as usize);
+                bytes.splice(start..start, chunk);
+            }
+            _ => bytes.truncate(at),
+        }
+    }
+    bytes
+}
+
+pub fn panic_message(
+    solution: &dyn Solution,
+    part: Part,
+    input: &str,
+    params: &Params,
+) -> Option<String> {
+    catch_panic(|| {
+        let _ = run_part(solution, part, input, params);
+    })
+    .err()
+}
+
+pub fn catch_panic<T>(f: impl FnOnce() -> T) -> std::result::Result<T, String> {
+    panic::catch_unwind(AssertUnwindSafe(f)).map_err(|payload| describe_panic(payload.as_ref()))
+}
+
+pub fn find_crash(
+    solution: &dyn Solution,
+    parts: &[Part],
+    params: &Params,
+    input: &str,
+    mutations: u32,
+    rng: &mut Rng,
+) -> Option<Crash> {
+    for _ in 0..mutations {
+        let mutated = String::from_utf8_lossy(&mutate(input.as_bytes(), rng)).into_owned();
+
+        for part in parts.iter().copied() {
+            if panic_message(solution, part, &mutated, params).is_some() {
+                let input = minimize(&mutated, |candidate| {
+                    panic_message(solution, part, candidate, params).is_some()
+                });
+                let message = panic_message(solution, part, &input, params).unwrap_or_default();
+                return Some(Crash {
+                    day: solution.day(),
+                    part,
+                    input,
+                    message,
+                });
+            }
+        }
+    }
+    None
+}
+
+pub fn minimize(input: &str, mut fails: impl FnMut(&str) -> bool) -> String {
+    let lines = ddmin(input.split_inclusive('\n').collect(), &mut fails).concat();
+    let tokens = ddmin(tokens(&lines), &mut fails).concat();
+    let chars = tokens
+        .char_indices()
+        .map(|(at, char)| &tokens[at..at + char.len_utf8()])
+        .collect();
+    ddmin(chars, &mut fails).concat()
+}
+
+pub fn write_regression(fixtures: &Path, crash: &Crash) -> Result<PathBuf> {
+    let dir = fixtures.join(REGRESSIONS_DIR);
+    fs::create_dir_all(&dir)?;
+
+    let path = (1..)
+        .map(|key| dir.join(format!("day{:02}-{key}.txt", crash.day)))
+        .find(|path| !path.exists())
+        .expect("there is always a free file name");
+    fs::write(&path, &crash.input)?;
+    Ok(path)
+}
+
+pub fn load_regressions(fixtures: &Path) -> Result<Vec<(u8, PathBuf, String)>> {
+    let dir = fixtures.join(REGRESSIONS_DIR);
+    let mut regressions = vec![];
+
+    if !dir.exists() {
+        return Ok(regressions);
+    }
+    for entry in fs::read_dir(&dir)? {
+        let path = entry?.path();
+        let day = path
+            .file_name()
+            .and_then(|name| name.to_str())
+            .and_then(|name| name.strip_prefix("day"))
+            .and_then(|name| name.get(..2))
+            .and_then(|day| day.parse().ok());
+        if let Some(day) = day {
+            let input = fs::read_to_string(&path)?;
+            regressions.push((day, path, input));
+        }
+    }
+    regressions.sort();
+    Ok(regressions)
+}
+
+fn ddmin<'a>(mut units: Vec<&'a str>, fails: &mut impl FnMut(&str) -> bool) -> Vec<&'a str> {
+    let mut chunks = 2;
+
+    while units.len() >= 2 {
+        let size = units.len().div_ceil(chunks);
+        let ranges: Vec<(usize, usize)> = (0..units.len())
+            .step_by(size)
+            .map(|start| (start, (start + size).min(units.len())))
+            .collect();
+
+        if let Some(&(start, end)) = ranges
+            .iter()
+            .find(|(start, end)| fails(&units[*start..*end].concat()))
+        {
+            units = units[start..end].to_vec();
+            chunks = 2;
+            continue;
+        }
+        let complement = |start: usize, end: usize| -> Vec<&'a str> {
+            units[..start]
+                .iter()
+                .chain(&units[end..])
+                .copied()
+                .collect()
+        };
+        if let Some(&(start, end)) = ranges
+            .iter()
+            .find(|(start, end)| fails(&complement(*start, *end).concat()))
+        {
+            units = complement(start, end);
+            chunks = (chunks - 1).max(2);
+            continue;
+        }
+        if chunks >= units.len() {
+            break;
+        }
+        chunks = (chunks * 2).min(units.len());
+    }
+    units
+}
+
+fn tokens(input: &str) -> Vec<&str> {
+    let mut tokens = vec![];
+    let mut start = 0;
+
+    for (at, char) in input.char_indices() {
+        let continues_word = char.is_alphanumeric()
+            && input[start..at]
+                .chars()
+                .last()
+                .is_some_and(char::is_alphanumeric);
+        if at > start && !continues_word {
+            tokens.push(&input[start..at]);
+            start = at;
+        }
+    }
+    if start < input.len() {
+        tokens.push(&input[start..]);
+    }
+    tokens
+}
+
+fn describe_panic(payload: &(dyn Any + Send)) -> String {
+    if let Some(message) = payload.downcast_ref::<&str>() {
+        message.to_string()
+    } else if let Some(message) = payload.downcast_ref::<String>() {
+        message.clone()
+    } else {
+        String::from("panicked")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::Path;
+
+    use crate::{
+        cli::Part,
+        fixtures::DEFAULT_FIXTURES_DIR,
+        input::{day_input_path, DEFAULT_INPUT_DIR},
+        params::Params,
+        rng::Rng,
+        solution::{self, SOLUTIONS},
+    };
+
+    use super::{
+        catch_panic, find_crash, load_regressions, minimize, mutate, panic_message, tokens,
+    };
+
+    #[test]
+    fn inputs_are_mutated_reproducibly() {
+        let input = b"Time: 7 15 30\nDistance: 9 40 200\n";
+        let mutated: Vec<Vec<u8>> = (0..20).map(|_| mutate(input, &mut Rng::new(4))).collect();
+
+        assert!(mutated.iter().all(|bytes| *bytes == mutated[0]));
+        let mut rng = Rng::new(4);
+        assert!((0..20).any(|_| mutate(input, &mut rng) != input));
+        assert!(!mutate(b"", &mut rng).is_empty());
+    }
+
+    #[test]
+    fn panics_are_caught_with_their_message() {
+        let hook = std::panic::take_hook();
+        std::panic::set_hook(Box::new(|_| {}));
+        let caught = catch_panic(|| -> u8 { panic!("card {} overflowed", 40) });
+        std::panic::set_hook(hook);
+
+        assert_eq!(caught, Err(String::from("card 40 overflowed")));
+        assert_eq!(catch_panic(|| 7), Ok(7));
+    }
+
+    #[test]
+    fn failing_inputs_are_minimized() {
+        assert_eq!(minimize("abc\nX\ndefgh", |input| input.contains('X')), "X");
+        assert_eq!(
+            minimize("12 + 34 = 46", |input| input.matches('4').count() == 2),
+            "44"
+        );
+    }
+
+    #[test]
+    fn inputs_are_split_into_tokens() {
+        assert_eq!(
+            tokens("Card 12: 41 | 83\n..#"),
+            vec!["Card", " ", "12", ":", " ", "41", " ", "|", " ", "83", "\n", ".", ".", "#"]
+        );
+        assert!(tokens("").is_empty());
+    }
+
+    #[test]
+    fn lines_and_then_tokens_are_removed() {
+        let input = "0 3 6 9\n1 3 6 10\n10 13 16 21 boom 7\n2 4\n";
+        assert_eq!(minimize(input, |input| input.contains("boom")), "boom");
+        assert_eq!(
+            minimize(input, |input| input.lines().count() >= 2
+                && input.contains("10")),
+            "\n10"
+        );
+    }
+
+    #[test]
+    fn mutated_real_inputs_do_not_panic() {
+        for solution in SOLUTIONS {
+            let path = day_input_path(Path::new(DEFAULT_INPUT_DIR), solution.day());
+            let Ok(input) = std::fs::read_to_string(path) else {
+                continue;
+            };
+            let params = Params::new(solution.params());
+            let mut rng = Rng::new(solution.day().into());
+
+            if let Some(crash) = find_crash(
+                *solution,
+                &[Part::One, Part::Two],
+                &params,
+                &input,
+                100,
+                &mut rng,
+            ) {
+                panic!(
+                    "day {} part {} panicked with `{}` on:\n{}",
+                    crash.day,
+                    crash.part.number(),
+                    crash.message,
+                    crash.input
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn regressions_do_not_panic() {
+        for (day, path, input) in load_regressions(Path::new(DEFAULT_FIXTURES_DIR)).unwrap() {
+            let solution = solution::get(day).unwrap();
+            let params = Params::new(solution.params());
+
+            for part in [Part::One, Part::Two] {
+                if let Some(message) = panic_message(solution, part, &input, &params) {
+                    panic!("{} part {}: {message}", path.display(), part.number());
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,732 @@
+use std::{
+    collections::BTreeMap,
+    path::Path,
+    process::ExitCode,
+    thread,
+    time::{Duration, Instant},
+};
+
+use crate::{
+    alloc,
+    answers::ExpectedAnswers,
+    bench,
+    cli::{
+        BenchArgs, CompareArgs, DaySelection, ExamplesArgs, FetchArgs, FuzzArgs, GenerateArgs,
+        NewArgs, Part, RunArgs, ShrinkArgs, SubmitArgs, VerifyArgs, WatchArgs, FIRST_DAY, LAST_DAY,
+    },
+    client::{Client, FetchOutcome},
+    config::Config,
+    differential,
+    error::{AocError, Result},
+    fixtures, generate,
+    input::{day_input_path, InputSource},
+    params::{self, Params},
+    pool,
+    report::{self, Format, Record, Status},
+    rng::Rng,
+    robustness, scaffold,
+    shrink::{self, Predicate},
+    solution::{self, Answer, Solution, SOLUTIONS},
+    submit::{self, Submission, SubmissionLog, Verdict},
+    trace,
+    watch::{self, Outcome, Snapshot},
+};
+
+pub fn run(args: RunArgs, config: &Config) -> ExitCode {
+    configure_trace(args.trace.as_ref());
+    let Some(solutions) = select_solutions(args.days, &args.params) else {
+        return ExitCode::FAILURE;
+    };
+    let inputs: Vec<_> = solutions
+        .into_iter()
+        .map(|solution| {
+            let day = solution.day();
+            (
+                solution,
+                config.params(solution, &args.params),
+                args.input.describe(day),
+                args.input.read(day),
+            )
+        })
+        .collect();
+    let tasks: Vec<_> = inputs
+        .iter()
+        .flat_map(|day_input| args.parts.iter().map(move |part| (day_input, *part)))
+        .collect();
+
+    let start = Instant::now();
+    let records = pool::map_parallel(
+        &tasks,
+        args.jobs,
+        |((solution, params, input_path, input), part)| {
+            let start = Instant::now();
+            let mut allocs = None;
+            let status = match (input, params) {
+                (Ok(input), Ok(params)) => {
+                    let (status, stats) =
+                        alloc::measure(|| part_status(*solution, *part, input, params));
+                    allocs = stats;
+                    status
+                }
+                (Err(err), _) => {
+                    Status::Error(format!("could not read input from {input_path}: {err}"))
+                }
+                (_, Err(err)) => Status::Error(format!("{err:#}")),
+            };
+            Record {
+                day: solution.day(),
+                title: solution.title(),
+                part: *part,
+                status,
+                duration: start.elapsed(),
+                input: input_path.clone(),
+                allocs,
+            }
+        },
+    );
+    let wall_time = start.elapsed();
+
+    match args.format {
+        Format::Text => report::print_text(&records, args.quiet),
+        Format::Json => println!("{}", report::render_json(&records)),
+        Format::Csv => println!("{}", report::render_csv(&records)),
+    }
+    if args.jobs > 1 && args.format == Format::Text && !args.quiet {
+        let part_time: Duration = records.iter().map(|record| record.duration).sum();
+        println!(
+            "{} parts on {} jobs in {} wall time, {} of part wall time added up",
+            records.len(),
+            args.jobs,
+            bench::format_duration(wall_time),
+            bench::format_duration(part_time)
+        );
+    }
+    if records
+        .iter()
+        .any(|record| matches!(record.status, Status::Error(_)))
+    {
+        ExitCode::FAILURE
+    } else {
+        ExitCode::SUCCESS
+    }
+}
+
+pub fn bench(args: BenchArgs, config: &Config) -> ExitCode {
+    let Some(solutions) = select_solutions(args.run.days, &args.run.params) else {
+        return ExitCode::FAILURE;
+    };
+    let mut exit_code = ExitCode::SUCCESS;
+
+    for solution in solutions {
+        let day = solution.day();
+        println!(
+            "Day {day}: {} ({} iterations)",
+            solution.title(),
+            args.iterations
+        );
+        let input = match args.run.input.read(day) {
+            Ok(input) => input,
+            Err(err) => {
+                eprintln!(
+                    "Day {day}: could not read input from {}: {err}",
+                    args.run.input.describe(day)
+                );
+                exit_code = ExitCode::FAILURE;
+                continue;
+            }
+        };
+        let params = match config.params(solution, &args.run.params) {
+            Ok(params) => params,
+            Err(err) => {
+                eprintln!("Day {day}: {err:#}");
+                exit_code = ExitCode::FAILURE;
+                continue;
+            }
+        };
+        for part in args.run.parts.iter() {
+            match bench::bench_part(solution, *part, &input, &params, args.iterations) {
+                Ok(Some(timings)) => {
+                    println!("    part {} parse  {}", part.number(), timings.parse);
+                    println!("    part {} solve  {}", part.number(), timings.solve);
+                }
+                Ok(None) => println!("    part {}: not implemented yet", part.number()),
+                Err(err) => {
+                    eprintln!("Day {day} part {} failed: {err:#}", part.number());
+                    exit_code = ExitCode::FAILURE;
+                }
+            }
+        }
+    }
+    exit_code
+}
+
+pub fn verify(args: VerifyArgs, config: &Config) -> ExitCode {
+    let expected = match ExpectedAnswers::read(&args.answers) {
+        Ok(expected) => expected,
+        Err(err) => {
+            eprintln!(
+                "error: could not read answers from {}: {err:#}",
+                args.answers.display()
+            );
+            return ExitCode::FAILURE;
+        }
+    };
+    configure_trace(args.run.trace.as_ref());
+    let Some(solutions) = select_solutions(args.run.days, &args.run.params) else {
+        return ExitCode::FAILURE;
+    };
+    let (mut verified, mut mismatches) = (0, 0);
+
+    for solution in solutions {
+        let day = solution.day();
+        if !args.run.quiet {
+            println!("Day {day}: {}", solution.title());
+        }
+        let input = match args.run.input.read(day) {
+            Ok(input) => input,
+            Err(err) => {
+                eprintln!(
+                    "Day {day}: could not read input from {}: {err}",
+                    args.run.input.describe(day)
+                );
+                mismatches += 1;
+                continue;
+            }
+        };
+        let params = match config.params(solution, &args.run.params) {
+            Ok(params) => params,
+            Err(err) => {
+                eprintln!("Day {day}: {err:#}");
+                mismatches += 1;
+                continue;
+            }
+        };
+        for part in args.run.parts.iter() {
+            let (status, matched) = match (
+                run_part(solution, *part, &input, &params),
+                expected.get(day, *part),
+            ) {
+                (Ok(Some(answer)), Some(expected)) if answer.to_string() == expected => {
+                    (format!("{answer} ok"), Some(true))
+                }
+                (Ok(Some(answer)), Some(expected)) => {
+                    (format!("{answer}, expected {expected}"), Some(false))
+                }
+                (Ok(Some(answer)), None) => (format!("{answer} (no recorded answer)"), None),
+                (Ok(None), Some(expected)) => (
+                    format!("not implemented yet, expected {expected}"),
+                    Some(false),
+                ),
+                (Ok(None), None) => (String::from("not implemented yet"), None),
+                (Err(err), _) => (format!("failed: {err:#}"), Some(false)),
+            };
+            match matched {
+                Some(false) => {
+                    mismatches += 1;
+                    eprintln!("Day {day} part {} mismatch: {status}", part.number());
+                    continue;
+                }
+                Some(true) => verified += 1,
+                None => (),
+            }
+            if !args.run.quiet {
+                println!("    part {}: {status}", part.number());
+            }
+        }
+    }
+
+    println!("{verified} parts verified, {mismatches} mismatched");
+    if mismatches == 0 {
+        ExitCode::SUCCESS
+    } else {
+        ExitCode::FAILURE
+    }
+}
+
+pub fn new_day(args: NewArgs) -> ExitCode {
+    match scaffold::create_day(&args.src, args.day, args.title.as_deref()) {
+        Ok(written) => {
+            for path in written {
+                println!("wrote {}", path.display());
+            }
+            ExitCode::SUCCESS
+        }
+        Err(err) => {
+            eprintln!("error: could not create day {}: {err:#}", args.day);
+            ExitCode::FAILURE
+        }
+    }
+}
+
+pub fn fetch(args: FetchArgs, config: &Config) -> ExitCode {
+    let mut client = match Client::from_config(config) {
+        Ok(client) => client,
+        Err(err) => {
+            eprintln!("error: {err:#}");
+            return ExitCode::FAILURE;
+        }
+    };
+    let days = match args.days {
+        DaySelection::All => SOLUTIONS.iter().map(|solution| solution.day()).collect(),
+        DaySelection::Days(days) => days,
+    };
+    let mut exit_code = ExitCode::SUCCESS;
+
+    for day in days {
+        let path = day_input_path(&args.input_dir, day);
+        match client.fetch_input(day, &path) {
+            Ok(FetchOutcome::Cached) => println!("Day {day}: cached at {}", path.display()),
+            Ok(FetchOutcome::Downloaded) => {
+                println!("Day {day}: downloaded to {}", path.display())
+            }
+            Err(err) => {
+                eprintln!("Day {day}: could not fetch input: {err:#}");
+                exit_code = ExitCode::FAILURE;
+            }
+        }
+    }
+    exit_code
+}
+
+pub fn submit(args: SubmitArgs, config: &Config) -> ExitCode {
+    match submit_answer(&args, config) {
+        Ok(Verdict::Correct) => {
+            println!(
+                "Day {} part {}: {}",
+                args.day,
+                args.part.number(),
+                Verdict::Correct
+            );
+            ExitCode::SUCCESS
+        }
+        Ok(verdict) => {
+            println!("Day {} part {}: {verdict}", args.day, args.part.number());
+            ExitCode::FAILURE
+        }
+        Err(err) => {
+            eprintln!("error: {err:#}");
+            ExitCode::FAILURE
+        }
+    }
+}
+
+fn submit_answer(args: &SubmitArgs, config: &Config) -> Result<Verdict> {
+    let solution = solution::get(args.day)
+        .ok_or_else(|| AocError::Refused(format!("day {} is not implemented yet", args.day)))?;
+    let input = args.input.read(args.day)?;
+    let params = config.params(solution, &args.params)?;
+    let answer = run_part(solution, args.part, &input, &params)?.ok_or_else(|| {
+        AocError::Refused(format!(
+            "day {} part {} is not implemented yet",
+            args.day,
+            args.part.number()
+        ))
+    })?;
+
+    SubmissionLog::read(&args.log)?.check(args.day, args.part, &answer)?;
+    let verdict = Client::from_config(config)?.submit_answer(args.day, args.part, &answer)?;
+    SubmissionLog::append(
+        &args.log,
+        &Submission {
+            timestamp: submit::now(),
+            day: args.day,
+            part: args.part,
+            answer: answer.to_string(),
+            verdict: verdict.clone(),
+        },
+    )?;
+    println!("Submitted {answer}");
+    Ok(verdict)
+}
+
+pub fn examples(args: ExamplesArgs) -> ExitCode {
+    let written = std::fs::read_to_string(&args.page)
+        .map_err(AocError::from)
+        .and_then(|page| {
+            let examples = fixtures::extract_examples(&page);
+            if examples.is_empty() {
+                return Err(AocError::parse("no `<pre><code>` examples found"));
+            }
+            fixtures::write_fixtures(&args.fixtures, args.day, &examples)
+        });
+
+    match written {
+        Ok(written) => {
+            for path in written {
+                println!("wrote {}", path.display());
+            }
+            ExitCode::SUCCESS
+        }
+        Err(err) => {
+            eprintln!(
+                "error: could not extract examples from {}: {err:#}",
+                args.page.display()
+            );
+            ExitCode::FAILURE
+        }
+    }
+}
+
+pub fn generate(args: GenerateArgs) -> ExitCode {
+    match generate::generate(args.day, args.size, &mut Rng::new(args.seed)) {
+        Some(input) => {
+            print!("{input}");
+            ExitCode::SUCCESS
+        }
+        None => {
+            let days: Vec<String> = (FIRST_DAY..=LAST_DAY)
+                .filter(|day| generate::supports(*day))
+                .map(|day| day.to_string())
+                .collect();
+            eprintln!(
+                "error: no generator for day {}, expected one of {}",
+                args.day,
+                days.join(", ")
+            );
+            ExitCode::FAILURE
+        }
+    }
+}
+
+pub fn fuzz(args: FuzzArgs, config: &Config) -> ExitCode {
+    let Some(solutions) = select_solutions(args.run.days, &args.run.params) else {
+        return ExitCode::FAILURE;
+    };
+    let mut rng = Rng::new(args.seed);
+    let mut exit_code = ExitCode::SUCCESS;
+    let hook = std::panic::take_hook();
+    std::panic::set_hook(Box::new(|_| {}));
+
+    for solution in solutions {
+        let day = solution.day();
+        let input = match args.run.input.read(day) {
+            Ok(input) => input,
+            Err(err) => {
+                eprintln!(
+                    "Day {day}: could not read input from {}: {err}",
+                    args.run.input.describe(day)
+                );
+                exit_code = ExitCode::FAILURE;
+                continue;
+            }
+        };
+        let params = match config.params(solution, &args.run.params) {
+            Ok(params) => params,
+            Err(err) => {
+                eprintln!("Day {day}: {err:#}");
+                exit_code = ExitCode::FAILURE;
+                continue;
+            }
+        };
+
+        let crash = robustness::find_crash(
+            solution,
+            &args.run.parts,
+            &params,
+            &input,
+            args.mutations,
+            &mut rng,
+        );
+        let Some(crash) = crash else {
+            println!(
+                "Day {day}: no panics in {} corrupted inputs",
+                args.mutations
+            );
+            continue;
+        };
+        exit_code = ExitCode::FAILURE;
+        println!(
+            "Day {day} part {} panicked: {}",
+            crash.part.number(),
+            crash.message
+        );
+        match robustness::write_regression(&args.fixtures, &crash) {
+            Ok(path) => println!("    wrote {}", path.display()),
+            Err(err) => eprintln!("    could not record the input: {err:#}"),
+        }
+    }
+
+    std::panic::set_hook(hook);
+    exit_code
+}
+
+pub fn compare(args: CompareArgs) -> ExitCode {
+    let pairs: Vec<&differential::Pair> = if args.pairs.is_empty() {
+        differential::PAIRS.iter().collect()
+    } else {
+        args.pairs
+            .iter()
+            .filter_map(|name| differential::get(name))
+            .collect()
+    };
+    let mut rng = Rng::new(args.seed);
+    let mut exit_code = ExitCode::SUCCESS;
+
+    for pair in pairs {
+        let Some(divergence) = differential::cross_check(pair, args.cases, args.size, &mut rng)
+        else {
+            println!("{}: agreed on {} generated inputs", pair.name, args.cases);
+            continue;
+        };
+        exit_code = ExitCode::FAILURE;
+        println!(
+            "{}: diverged on case {} of {}",
+            divergence.name,
+            divergence.case + 1,
+            args.cases
+        );
+        println!("    reference: {}", divergence.reference);
+        println!("    optimized: {}", divergence.optimized);
+        println!("    input:\n{}", divergence.input);
+    }
+    exit_code
+}
+
+pub fn shrink(args: ShrinkArgs, config: &Config) -> ExitCode {
+    let day = args.day;
+    let Some(solution) = solution::get(day) else {
+        eprintln!("Day {day} is not implemented yet");
+        return ExitCode::FAILURE;
+    };
+    let input = match args.input.read(day) {
+        Ok(input) => input,
+        Err(err) => {
+            eprintln!(
+                "error: could not read input from {}: {err}",
+                args.input.describe(day)
+            );
+            return ExitCode::FAILURE;
+        }
+    };
+    let params = match config.params(solution, &args.params) {
+        Ok(params) => params,
+        Err(err) => {
+            eprintln!("error: {err:#}");
+            return ExitCode::FAILURE;
+        }
+    };
+    let hook = std::panic::take_hook();
+    std::panic::set_hook(Box::new(|_| {}));
+
+    let candidates = match (&args.pair, &args.expected, args.part) {
+        (Some(name), _, _) => differential::get(name)
+            .map(Predicate::Diverges)
+            .into_iter()
+            .collect(),
+        (None, Some(expected), Some(part)) => vec![Predicate::Differs(part, expected.clone())],
+        (None, _, Some(part)) => vec![Predicate::Panics(part)],
+        (None, _, None) => vec![Predicate::Panics(Part::One), Predicate::Panics(Part::Two)],
+    };
+    let predicate = candidates
+        .into_iter()
+        .find(|predicate| predicate.holds(solution, &input, &params));
+    let Some(predicate) = predicate else {
+        std::panic::set_hook(hook);
+        eprintln!(
+            "error: the input from {} does not {}",
+            args.input.describe(day),
+            match (&args.pair, &args.expected) {
+                (Some(name), _) => format!("make `{name}` diverge"),
+                (None, Some(expected)) => format!("give an answer other than {expected}"),
+                (None, None) => String::from("panic"),
+            }
+        );
+        return ExitCode::FAILURE;
+    };
+
+    let shrunk = robustness::minimize(&input, |candidate| {
+        predicate.holds(solution, candidate, &params)
+    });
+    let written = shrink::write_reproducer(&args.fixtures, solution, predicate, &shrunk, &params);
+    std::panic::set_hook(hook);
+
+    println!(
+        "Day {day}: shrunk {} lines ({} bytes) to {} lines ({} bytes)",
+        input.lines().count(),
+        input.len(),
+        shrunk.lines().count(),
+        shrunk.len()
+    );
+    match written {
+        Ok(path) => {
+            println!("    wrote {}", path.display());
+            ExitCode::SUCCESS
+        }
+        Err(err) => {
+            eprintln!("    could not record the input: {err:#}");
+            ExitCode::FAILURE
+        }
+    }
+}
+
+pub fn watch(args: WatchArgs, config: &Config) -> ExitCode {
+    configure_trace(args.run.trace.as_ref());
+    let Some(solutions) = select_solutions(args.run.days, &args.run.params) else {
+        return ExitCode::FAILURE;
+    };
+    let [solution] = solutions[..] else {
+        return ExitCode::FAILURE;
+    };
+    let params = match config.params(solution, &args.run.params) {
+        Ok(params) => params,
+        Err(err) => {
+            eprintln!("error: {err:#}");
+            return ExitCode::FAILURE;
+        }
+    };
+    let day = solution.day();
+    let mut previous = BTreeMap::new();
+    let mut last = Snapshot::new();
+
+    println!(
+        "Watching day {day}: {}, press Ctrl-C to stop",
+        solution.title()
+    );
+    loop {
+        let current = watch::snapshot(&watch::watched_paths(&args.run.input, &args.fixtures, day));
+        let changed = watch::changed_paths(&last, &current);
+
+        if !changed.is_empty() {
+            if !last.is_empty() {
+                let changed: Vec<String> = changed
+                    .iter()
+                    .map(|path| path.display().to_string())
+                    .collect();
+                println!("\nChanged: {}", changed.join(", "));
+            }
+            for (label, part, outcome) in run_watched(
+                solution,
+                &args.run.input,
+                &args.run.parts,
+                &args.fixtures,
+                &params,
+            ) {
+                println!(
+                    "    {label}part {}: {}",
+                    part.number(),
+                    watch::describe(&outcome, previous.get(&(label.clone(), part)))
+                );
+                previous.insert((label, part), outcome);
+            }
+            last = current;
+        }
+        thread::sleep(args.interval);
+    }
+}
+
+fn run_watched(
+    solution: &dyn Solution,
+    input: &InputSource,
+    parts: &[Part],
+    fixtures: &Path,
+    params: &Params,
+) -> Vec<(String, Part, Outcome)> {
+    let day = solution.day();
+    let mut inputs = vec![(String::new(), input.read(day), vec![])];
+
+    match fixtures::load_day_fixtures(fixtures, day) {
+        Ok(fixtures) => inputs.extend(fixtures.into_iter().map(|fixture| {
+            (
+                format!("{} ", fixture.name),
+                Ok(fixture.input),
+                fixture.answers,
+            )
+        })),
+        Err(err) => eprintln!("Day {day}: could not load examples: {err:#}"),
+    }
+
+    let mut outcomes = vec![];
+    for part in parts.iter().copied() {
+        for (label, contents, answers) in inputs.iter() {
+            let expected = answers
+                .iter()
+                .find(|(answer_part, _)| *answer_part == part)
+                .map(|(_, answer)| answer.clone());
+            if !label.is_empty() && expected.is_none() {
+                continue;
+            }
+
+            let start = Instant::now();
+            let status = match contents {
+                Ok(contents) => part_status(solution, part, contents, params),
+                Err(err) => Status::Error(format!(
+                    "could not read input from {}: {err}",
+                    input.describe(day)
+                )),
+            };
+            let outcome = Outcome {
+                status,
+                expected,
+                duration: start.elapsed(),
+            };
+            outcomes.push((label.clone(), part, outcome));
+        }
+    }
+    outcomes
+}
+
+fn select_solutions(
+    days: DaySelection,
+    overrides: &[(String, String)],
+) -> Option<Vec<&'static dyn Solution>> {
+    let days = match days {
+        DaySelection::All => SOLUTIONS.iter().map(|solution| solution.day()).collect(),
+        DaySelection::Days(days) => days,
+    };
+    let mut solutions = vec![];
+
+    for day in days {
+        match solution::get(day) {
+            Some(solution) => solutions.push(solution),
+            None => eprintln!("Day {day} is not implemented yet"),
+        }
+    }
+    if solutions.is_empty() {
+        eprintln!("error: none of the selected days is implemented");
+        return None;
+    }
+    for (key, _) in overrides {
+        let declared =
+            |solution: &&dyn Solution| solution.params().iter().any(|param| param.name == key);
+        if !solutions.iter().any(declared) {
+            eprintln!("error: none of the selected days has a parameter `{key}`");
+            for solution in solutions.iter() {
+                eprintln!(
+                    "    day {}: {}",
+                    solution.day(),
+                    params::describe(solution.params())
+                );
+            }
+            return None;
+        }
+    }
+    Some(solutions)
+}
+
+pub fn run_part(
+    solution: &dyn Solution,
+    part: Part,
+    input: &str,
+    params: &Params,
+) -> Result<Option<Answer>> {
+    trace::in_day(solution.day(), || match part {
+        Part::One => solution.part_one(input, params).map(Some),
+        Part::Two => solution.part_two(input, params),
+    })
+}
+
+fn part_status(solution: &dyn Solution, part: Part, input: &str, params: &Params) -> Status {
+    match robustness::catch_panic(|| run_part(solution, part, input, params)) {
+        Ok(Ok(Some(answer))) => Status::Ok(answer),
+        Ok(Ok(None)) => Status::Unimplemented,
+        Ok(Err(err)) => Status::Error(format!("{err:#}")),
+        Err(message) => Status::Error(format!("panicked: {message}")),
+    }
+}
+
+fn configure_trace(filter: Option<&trace::Filter>) {
+    if filter.is_some() && !trace::available() {
+        eprintln!("warning: tracing is compiled out of release builds, run a debug build instead");
+    }
+    trace::configure(filter);
+}
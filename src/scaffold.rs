@@ -0,0 +1,274 @@
+use std::{
+    fs, io,
+    path::{Path, PathBuf},
+};
+
+use crate::error::{AocError, Result};
+
+pub const DEFAULT_SOURCE_DIR: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/src");
+
+const MAX_WIDTH: usize = 100;
+const SOLUTIONS_START: &str = "pub static SOLUTIONS: &[&dyn Solution] = &[\n";
+
+pub fn create_day(src: &Path, day: u8, title: Option<&str>) -> Result<Vec<PathBuf>> {
+    let module_dir = src.join(format!("day{day:02}"));
+    let module_path = module_dir.join("mod.rs");
+    let input_path = module_dir.join("input.txt");
+    let lib_path = src.join("lib.rs");
+    let solution_path = src.join("solution.rs");
+
+    if module_path.exists() {
+        return Err(AocError::Io(io::Error::new(
+            io::ErrorKind::AlreadyExists,
+            format!("{} already exists", module_path.display()),
+        )));
+    }
+    let lib = register_module(&fs::read_to_string(&lib_path)?, day)?;
+    let solutions = register_solution(&fs::read_to_string(&solution_path)?, day)?;
+    let title = title.map_or_else(|| format!("Day {day}"), String::from);
+
+    fs::create_dir_all(&module_dir)?;
+    fs::write(&module_path, module_template(day, &title))?;
+    let mut written = vec![module_path];
+    if !input_path.exists() {
+        fs::write(&input_path, "")?;
+        written.push(input_path);
+    }
+    fs::write(&lib_path, lib)?;
+    fs::write(&solution_path, solutions)?;
+    written.extend([lib_path, solution_path]);
+    Ok(written)
+}
+
+pub fn module_template(day: u8, title: &str) -> String {
+    format!(
+        r#"use crate::{{
+    error::Result,
+    params::Params,
+    solution::{{Solution, Solver}},
+}};
+
+pub struct Day{day:02};
+
+impl Solution for Day{day:02} {{
+    fn day(&self) -> u8 {{
+        {day}
+    }}
+
+    fn title(&self) -> &'static str {{
+        "{title}"
+    }}
+
+    fn parse_part_one<'a>(&self, input: &'a str, _params: &Params) -> Result<Solver<'a>> {{
+        let lines = parse_input(input)?;
+
+        Ok(Box::new(move || Ok(lines.len().into())))
+    }}
+}}
+
+pub fn parse_input(input: &str) -> Result<Vec<&str>> {{
+    Ok(input.lines().collect())
+}}
+
+#[cfg(test)]
+mod tests {{
+    use super::parse_input;
+
+    const EXAMPLE: &str = "";
+
+    #[test]
+    fn part_one_works() {{
+        let lines = parse_input(EXAMPLE).unwrap();
+
+        assert_eq!(lines.len(), 0);
+    }}
+}}
+"#,
+        title = title.escape_default()
+    )
+}
+
+pub fn register_module(lib: &str, day: u8) -> Result<String> {
+    let declaration = format!("pub mod day{day:02};");
+    if lib.lines().any(|line| line.trim() == declaration) {
+        return Err(AocError::parse(format!("`{declaration}` already exists")));
+    }
+
+    let mut lines: Vec<&str> = lib.lines().collect();
+    let position = lines
+        .iter()
+        .position(|line| line.starts_with("pub mod ") && **line > *declaration)
+        .unwrap_or(lines.len());
+    lines.insert(position, &declaration);
+    Ok(lines.join("\n") + "\n")
+}
+
+pub fn register_solution(solution: &str, day: u8) -> Result<String> {
+    let module = format!("day{day:02}");
+
+    let import_start = solution
+        .find("use crate::{")
+        .ok_or_else(|| AocError::parse("could not find the `use crate::{...}` import"))?;
+    let import_end = import_start
+        + solution[import_start..]
+            .find("};")
+            .ok_or_else(|| AocError::parse("unterminated `use crate::{...}` import"))?
+        + 2;
+    let mut imports = split_imports(&solution[import_start + "use crate::{".len()..import_end - 2]);
+    if imports.contains(&module) {
+        return Err(AocError::parse(format!("`{module}` is already registered")));
+    }
+    imports.push(module.clone());
+    imports.sort();
+
+    let list_start = solution
+        .find(SOLUTIONS_START)
+        .ok_or_else(|| AocError::parse("could not find the `SOLUTIONS` registry"))?
+        + SOLUTIONS_START.len();
+    let list_end = list_start
+        + solution[list_start..]
+            .find("];")
+            .ok_or_else(|| AocError::parse("unterminated `SOLUTIONS` registry"))?;
+    let mut entries: Vec<&str> = solution[list_start..list_end].lines().collect();
+    let entry = format!("    &{module}::Day{day:02},");
+    let position = entries
+        .iter()
+        .position(|line| **line > *entry)
+        .unwrap_or(entries.len());
+    entries.insert(position, &entry);
+
+    Ok(format!(
+        "{}{}{}{}\n{}",
+        &solution[..import_start],
+        format_import(&imports),
+        &solution[import_end..list_start],
+        entries.join("\n"),
+        &solution[list_end..]
+    ))
+}
+
+fn split_imports(list: &str) -> Vec<String> {
+    let mut items = vec![];
+    let mut depth = 0;
+    let mut start = 0;
+
+    for (at, char) in list.char_indices() {
+        match char {
+            '{' => depth += 1,
+            '}' => depth -= 1,
+            ',' if depth == 0 => {
+                items.push(list[start..at].trim().to_string());
+                start = at + 1;
+            }
+            _ => (),
+        }
+    }
+    items.push(list[start..].trim().to_string());
+    items.retain(|item| !item.is_empty());
+    items
+}
+
+fn format_import(items: &[String]) -> String {
+    let single_line = format!("use crate::{{{}}};", items.join(", "));
+    if single_line.len() <= MAX_WIDTH {
+        return single_line;
+    }
+
+    let mut lines = vec![];
+    let mut line = String::from("   ");
+    for item in items {
+        if line.len() + item.len() + 2 > MAX_WIDTH {
+            lines.push(line);
+            line = String::from("   ");
+        }
+        line.push(' ');
+        line.push_str(item);
+        line.push(',');
+    }
+    lines.push(line);
+    format!("use crate::{{\n{}\n}};", lines.join("\n"))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+
+    use super::{create_day, register_module, register_solution, split_imports};
+
+    const SOLUTION: &str = "use std::fmt::Display;
+
+use crate::{day01, day03, error::Result};
+
+pub static SOLUTIONS: &[&dyn Solution] = &[
+    &day01::Day01,
+    &day03::Day03,
+];
+";
+
+    #[test]
+    fn days_are_registered_in_order() {
+        assert_eq!(
+            register_module("pub mod day01;\npub mod day03;\npub mod error;\n", 2).unwrap(),
+            "pub mod day01;\npub mod day02;\npub mod day03;\npub mod error;\n"
+        );
+        assert!(register_module("pub mod day01;\n", 1).is_err());
+        assert_eq!(
+            register_solution(SOLUTION, 2).unwrap(),
+            "use std::fmt::Display;
+
+use crate::{day01, day02, day03, error::Result};
+
+pub static SOLUTIONS: &[&dyn Solution] = &[
+    &day01::Day01,
+    &day02::Day02,
+    &day03::Day03,
+];
+"
+        );
+        assert!(register_solution(SOLUTION, 3).is_err());
+    }
+
+    #[test]
+    fn nested_imports_are_kept_intact() {
+        let registered = register_solution(include_str!("solution.rs"), 12).unwrap();
+        let import_start = registered.find("use crate::{").unwrap() + "use crate::{".len();
+        let import_end = import_start + registered[import_start..].find("};").unwrap();
+        let imports = split_imports(&registered[import_start..import_end]);
+
+        assert!(imports.contains(&String::from("day12")));
+        assert!(imports.contains(&String::from("params::{Param, Params}")));
+        assert!(imports
+            .iter()
+            .all(|item| item.matches('{').count() == item.matches('}').count()));
+        assert_eq!(
+            registered.matches('{').count(),
+            registered.matches('}').count()
+        );
+        assert!(registered.contains("    &day11::Day11,\n    &day12::Day12,\n];"));
+    }
+
+    #[test]
+    fn existing_modules_are_not_overwritten() {
+        let src = std::env::temp_dir().join(format!("aoc2023-scaffold-{}", std::process::id()));
+        fs::create_dir_all(src.join("day01")).unwrap();
+        fs::write(src.join("day01").join("mod.rs"), "// day 1").unwrap();
+        fs::write(src.join("lib.rs"), "pub mod day01;\npub mod day03;\n").unwrap();
+        fs::write(src.join("solution.rs"), SOLUTION).unwrap();
+
+        let written = create_day(&src, 2, Some("Cube Conundrum")).unwrap();
+        let module = fs::read_to_string(src.join("day02").join("mod.rs")).unwrap();
+
+        assert_eq!(written.len(), 4);
+        assert!(module.contains("pub struct Day02;"));
+        assert!(module.contains("\"Cube Conundrum\""));
+        assert!(src.join("day02").join("input.txt").exists());
+        assert!(create_day(&src, 1, None).is_err());
+        assert!(create_day(&src, 2, None).is_err());
+        assert_eq!(
+            fs::read_to_string(src.join("day01").join("mod.rs")).unwrap(),
+            "// day 1"
+        );
+
+        fs::remove_dir_all(&src).unwrap();
+    }
+}
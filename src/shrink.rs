@@ -0,0 +1,185 @@
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+use crate::{
+    cli::Part,
+    differential::{self, Pair},
+    error::Result,
+    fixtures::ANSWERS_FILE,
+    params::Params,
+    robustness::{self, catch_panic, panic_message, Crash},
+    runner::run_part,
+    solution::Solution,
+};
+
+pub const SHRUNK_DIR: &str = "shrunk";
+
+#[derive(Clone)]
+pub enum Predicate {
+    Panics(Part),
+    Differs(Part, String),
+    Diverges(&'static Pair),
+}
+
+impl Predicate {
+    pub fn holds(&self, solution: &dyn Solution, input: &str, params: &Params) -> bool {
+        match self {
+            Predicate::Panics(part) => panic_message(solution, *part, input, params).is_some(),
+            Predicate::Differs(part, expected) => {
+                answer(solution, *part, input, params).is_some_and(|answer| answer != *expected)
+            }
+            Predicate::Diverges(pair) => differential::diverges(pair, input).is_some(),
+        }
+    }
+}
+
+pub fn write_reproducer(
+    fixtures: &Path,
+    solution: &dyn Solution,
+    predicate: Predicate,
+    input: &str,
+    params: &Params,
+) -> Result<PathBuf> {
+    match predicate {
+        Predicate::Panics(part) => robustness::write_regression(
+            fixtures,
+            &Crash {
+                day: solution.day(),
+                part,
+                input: input.to_string(),
+                message: panic_message(solution, part, input, params).unwrap_or_default(),
+            },
+        ),
+        Predicate::Differs(part, expected) => {
+            let answer = answer(solution, part, input, params).unwrap_or_default();
+            write_example(fixtures, solution.day(), part, input, &expected, &answer)
+        }
+        Predicate::Diverges(pair) => differential::write_case(fixtures, pair, input),
+    }
+}
+
+fn answer(solution: &dyn Solution, part: Part, input: &str, params: &Params) -> Option<String> {
+    catch_panic(|| run_part(solution, part, input, params))
+        .ok()?
+        .ok()?
+        .map(|answer| answer.to_string())
+}
+
+fn write_example(
+    fixtures: &Path,
+    day: u8,
+    part: Part,
+    input: &str,
+    expected: &str,
+    answer: &str,
+) -> Result<PathBuf> {
+    let dir = fixtures.join(SHRUNK_DIR);
+    fs::create_dir_all(&dir)?;
+
+    let (name, path) = (1..)
+        .map(|key| format!("day{day:02}-{key}"))
+        .map(|name| (name.clone(), dir.join(format!("{name}.txt"))))
+        .find(|(_, path)| !path.exists())
+        .expect("there is always a free file name");
+    fs::write(&path, input)?;
+
+    let answers_path = dir.join(ANSWERS_FILE);
+    let mut answers = fs::read_to_string(&answers_path).unwrap_or_default();
+    if !answers.is_empty() && !answers.ends_with('\n') {
+        answers.push('\n');
+    }
+    if !answers.is_empty() {
+        answers.push('\n');
+    }
+    answers.push_str(&format!(
+        "[{name}]\n# part {} gives {answer} but the input it was shrunk from expects {expected};\n\
+         # move it to day{day:02}/ with the correct answer as `part{} = ...`\n",
+        part.number(),
+        part.number()
+    ));
+    fs::write(&answers_path, answers)?;
+    Ok(path)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        cli::Part,
+        differential::Pair,
+        fixtures::{load_fixtures, ANSWERS_FILE},
+        params::Params,
+        solution,
+    };
+
+    use super::{write_reproducer, Predicate, SHRUNK_DIR};
+
+    static ODD: Pair = Pair {
+        name: "odd",
+        day: 6,
+        generate: |_, _| String::new(),
+        reference: |input| (input.len() % 2).to_string(),
+        optimized: |_| String::from("0"),
+    };
+    static PANICKING: Pair = Pair {
+        name: "panicking",
+        day: 6,
+        generate: |_, _| String::new(),
+        reference: |input| input.to_string(),
+        optimized: |input| input[1..].to_string(),
+    };
+
+    #[test]
+    fn predicates_are_checked() {
+        let day06 = solution::get(6).unwrap();
+        let params = Params::new(day06.params());
+        let diverges = Predicate::Diverges(&ODD);
+
+        assert!(diverges.holds(day06, "abc", &params));
+        assert!(!diverges.holds(day06, "ab", &params));
+        assert!(!Predicate::Panics(Part::One).holds(day06, "Time: 7\nDistance: 9\n", &params));
+
+        let hook = std::panic::take_hook();
+        std::panic::set_hook(Box::new(|_| {}));
+        let panicked = Predicate::Diverges(&PANICKING).holds(day06, "é", &params);
+        std::panic::set_hook(hook);
+        assert!(panicked);
+
+        let differs = Predicate::Differs(Part::One, String::from("288"));
+        assert!(differs.holds(day06, "Time: 7\nDistance: 9\n", &params));
+        assert!(!differs.holds(day06, "Time: 7 15 30\nDistance: 9 40 200\n", &params));
+        assert!(!differs.holds(day06, "Time: 7\n", &params));
+    }
+
+    #[test]
+    fn differing_inputs_are_kept_out_of_the_fixtures() {
+        let dir = std::env::temp_dir().join(format!("aoc2023-shrink-{}", std::process::id()));
+        let day06 = solution::get(6).unwrap();
+        let params = Params::new(day06.params());
+        let differs = Predicate::Differs(Part::One, String::from("288"));
+
+        let paths: Vec<_> = (0..2)
+            .map(|_| {
+                write_reproducer(
+                    &dir,
+                    day06,
+                    differs.clone(),
+                    "Time: 7\nDistance: 9\n",
+                    &params,
+                )
+                .unwrap()
+            })
+            .collect();
+        assert_eq!(paths[1], dir.join(SHRUNK_DIR).join("day06-2.txt"));
+        assert_eq!(
+            std::fs::read_to_string(&paths[1]).unwrap(),
+            "Time: 7\nDistance: 9\n"
+        );
+        let notes = std::fs::read_to_string(dir.join(SHRUNK_DIR).join(ANSWERS_FILE)).unwrap();
+        assert!(notes.contains("[day06-2]\n# part 1 gives 4 but"));
+        assert!(load_fixtures(&dir).unwrap().is_empty());
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -0,0 +1,133 @@
+use std::fmt::Display;
+
+use crate::{
+    day01, day02, day03, day04, day05, day06, day07, day08, day09, day10, day11,
+    error::Result,
+    params::{Param, Params},
+};
+
+pub static SOLUTIONS: &[&dyn Solution] = &[
+    &day01::Day01,
+    &day02::Day02,
+    &day03::Day03,
+    &day04::Day04,
+    &day05::Day05,
+    &day06::Day06,
+    &day07::Day07,
+    &day08::Day08,
+    &day09::Day09,
+    &day10::Day10,
+    &day11::Day11,
+];
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Answer {
+    Integer(u64),
+    Signed(i64),
+    Text(String),
+}
+
+impl Display for Answer {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Answer::Integer(value) => value.fmt(f),
+            Answer::Signed(value) => value.fmt(f),
+            Answer::Text(value) => value.fmt(f),
+        }
+    }
+}
+
+impl From<u64> for Answer {
+    fn from(value: u64) -> Self {
+        Answer::Integer(value)
+    }
+}
+
+impl From<u32> for Answer {
+    fn from(value: u32) -> Self {
+        Answer::Integer(value.into())
+    }
+}
+
+impl From<usize> for Answer {
+    fn from(value: usize) -> Self {
+        Answer::Integer(value as u64)
+    }
+}
+
+impl From<i64> for Answer {
+    fn from(value: i64) -> Self {
+        Answer::Signed(value)
+    }
+}
+
+impl From<String> for Answer {
+    fn from(value: String) -> Self {
+        Answer::Text(value)
+    }
+}
+
+impl From<&str> for Answer {
+    fn from(value: &str) -> Self {
+        Answer::Text(value.to_string())
+    }
+}
+
+pub type Solver<'a> = Box<dyn FnOnce() -> Result<Answer> + 'a>;
+
+pub trait Solution: Sync {
+    fn day(&self) -> u8;
+    fn title(&self) -> &'static str;
+
+    fn params(&self) -> &'static [Param] {
+        &[]
+    }
+
+    fn parse_part_one<'a>(&self, input: &'a str, params: &Params) -> Result<Solver<'a>>;
+
+    fn parse_part_two<'a>(&self, _input: &'a str, _params: &Params) -> Result<Option<Solver<'a>>> {
+        Ok(None)
+    }
+
+    fn part_one(&self, input: &str, params: &Params) -> Result<Answer> {
+        self.parse_part_one(input, params)?()
+    }
+
+    fn part_two(&self, input: &str, params: &Params) -> Result<Option<Answer>> {
+        self.parse_part_two(input, params)?
+            .map(|solve| solve())
+            .transpose()
+    }
+}
+
+pub fn get(day: u8) -> Option<&'static dyn Solution> {
+    SOLUTIONS
+        .iter()
+        .find(|solution| solution.day() == day)
+        .copied()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{get, Answer, SOLUTIONS};
+
+    #[test]
+    fn registry_is_ordered_by_day() {
+        let days: Vec<u8> = SOLUTIONS.iter().map(|solution| solution.day()).collect();
+
+        assert!(days.windows(2).all(|pair| pair[0] < pair[1]));
+        assert!((1..=11).all(|day| days.contains(&day)));
+        assert_eq!(get(7).map(|solution| solution.title()), Some("Camel Cards"));
+        assert!(get(0).is_none());
+    }
+
+    #[test]
+    fn answers_are_typed_and_formatted() {
+        assert_eq!(Answer::from(42u32), Answer::Integer(42));
+        assert_eq!(Answer::from(7usize), Answer::Integer(7));
+        assert_eq!(Answer::from(-3i64), Answer::Signed(-3));
+        assert_eq!(Answer::from("ABC"), Answer::Text(String::from("ABC")));
+        assert_eq!(Answer::Signed(-3).to_string(), "-3");
+        assert_eq!(Answer::Text(String::from("ABC")).to_string(), "ABC");
+    }
+}
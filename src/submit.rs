@@ -0,0 +1,314 @@
+use std::{
+    fmt::Display,
+    fs::{self, OpenOptions},
+    io::{self, Write},
+    path::Path,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+use crate::{
+    cli::Part,
+    error::{AocError, Result},
+    solution::Answer,
+    utils::strip_tags,
+};
+
+pub const DEFAULT_LOG_FILE: &str = "submissions.log";
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Verdict {
+    Correct,
+    TooHigh,
+    TooLow,
+    Wrong,
+    RateLimited(Option<Duration>),
+    AlreadySolved,
+    Unknown(String),
+}
+
+impl Verdict {
+    pub fn from_response(body: &str) -> Self {
+        let text = strip_tags(body);
+
+        if text.contains("That's the right answer") {
+            Verdict::Correct
+        } else if text.contains("That's not the right answer") {
+            if text.contains("your answer is too high") {
+                Verdict::TooHigh
+            } else if text.contains("your answer is too low") {
+                Verdict::TooLow
+            } else {
+                Verdict::Wrong
+            }
+        } else if text.contains("You gave an answer too recently") {
+            Verdict::RateLimited(parse_wait(&text))
+        } else if text.contains("You don't seem to be solving the right level") {
+            Verdict::AlreadySolved
+        } else {
+            Verdict::Unknown(text.split_whitespace().collect::<Vec<_>>().join(" "))
+        }
+    }
+
+    fn name(&self) -> &'static str {
+        match self {
+            Verdict::Correct => "correct",
+            Verdict::TooHigh => "too-high",
+            Verdict::TooLow => "too-low",
+            Verdict::Wrong => "wrong",
+            Verdict::RateLimited(_) => "rate-limited",
+            Verdict::AlreadySolved => "already-solved",
+            Verdict::Unknown(_) => "unknown",
+        }
+    }
+
+    fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "correct" => Some(Verdict::Correct),
+            "too-high" => Some(Verdict::TooHigh),
+            "too-low" => Some(Verdict::TooLow),
+            "wrong" => Some(Verdict::Wrong),
+            "rate-limited" => Some(Verdict::RateLimited(None)),
+            "already-solved" => Some(Verdict::AlreadySolved),
+            "unknown" => Some(Verdict::Unknown(String::new())),
+            _ => None,
+        }
+    }
+}
+
+impl Display for Verdict {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Verdict::Correct => write!(f, "that's the right answer"),
+            Verdict::TooHigh => write!(f, "wrong, the answer is too high"),
+            Verdict::TooLow => write!(f, "wrong, the answer is too low"),
+            Verdict::Wrong => write!(f, "wrong answer"),
+            Verdict::RateLimited(Some(wait)) => {
+                write!(f, "rate limited, wait {}s", wait.as_secs())
+            }
+            Verdict::RateLimited(None) => write!(f, "rate limited"),
+            Verdict::AlreadySolved => write!(f, "this part is already solved"),
+            Verdict::Unknown(text) => write!(f, "unrecognised response: {text}"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Submission {
+    pub timestamp: u64,
+    pub day: u8,
+    pub part: Part,
+    pub answer: String,
+    pub verdict: Verdict,
+}
+
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct SubmissionLog {
+    pub submissions: Vec<Submission>,
+}
+
+impl SubmissionLog {
+    pub fn parse(input: &str) -> Result<Self> {
+        let mut log = SubmissionLog::default();
+
+        for (key, line) in input.lines().enumerate() {
+            if line.trim().is_empty() {
+                continue;
+            }
+            let malformed =
+                || AocError::parse(format!("malformed submission `{line}`")).with_line(key + 1);
+            let [timestamp, day, part, answer, verdict] = line.split('\t').collect::<Vec<_>>()[..]
+            else {
+                return Err(malformed());
+            };
+            log.submissions.push(Submission {
+                timestamp: timestamp.parse().map_err(|_| malformed())?,
+                day: day.parse().map_err(|_| malformed())?,
+                part: match part {
+                    "1" => Part::One,
+                    "2" => Part::Two,
+                    _ => return Err(malformed()),
+                },
+                answer: answer.to_string(),
+                verdict: Verdict::from_name(verdict).ok_or_else(malformed)?,
+            });
+        }
+        Ok(log)
+    }
+
+    pub fn read(path: &Path) -> Result<Self> {
+        match fs::read_to_string(path) {
+            Ok(contents) => Self::parse(&contents),
+            Err(err) if err.kind() == io::ErrorKind::NotFound => Ok(Self::default()),
+            Err(err) => Err(err.into()),
+        }
+    }
+
+    pub fn append(path: &Path, submission: &Submission) -> Result<()> {
+        let mut file = OpenOptions::new().create(true).append(true).open(path)?;
+        writeln!(
+            file,
+            "{}\t{}\t{}\t{}\t{}",
+            submission.timestamp,
+            submission.day,
+            submission.part.number(),
+            submission.answer,
+            submission.verdict.name()
+        )?;
+        Ok(())
+    }
+
+    pub fn check(&self, day: u8, part: Part, answer: &Answer) -> Result<()> {
+        let value = answer_value(answer);
+        let answer = answer.to_string();
+
+        for submission in self
+            .submissions
+            .iter()
+            .filter(|submission| submission.day == day && submission.part == part)
+        {
+            let refuse = |reason: String| {
+                Err(AocError::Refused(format!(
+                    "day {day} part {}: {reason}",
+                    part.number()
+                )))
+            };
+            let submitted = submission.answer.parse::<i128>().ok();
+
+            match (&submission.verdict, value, submitted) {
+                (Verdict::Correct, _, _) => {
+                    return refuse(format!("already solved with {}", submission.answer))
+                }
+                (Verdict::TooHigh | Verdict::TooLow | Verdict::Wrong, _, _)
+                    if submission.answer == answer =>
+                {
+                    return refuse(format!("{answer} was already rejected"))
+                }
+                (Verdict::TooHigh, Some(value), Some(bound)) if value >= bound => {
+                    return refuse(format!("{answer} is not below {bound}, which is too high"))
+                }
+                (Verdict::TooLow, Some(value), Some(bound)) if value <= bound => {
+                    return refuse(format!("{answer} is not above {bound}, which is too low"))
+                }
+                _ => (),
+            }
+        }
+        Ok(())
+    }
+}
+
+pub fn now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |duration| duration.as_secs())
+}
+
+fn answer_value(answer: &Answer) -> Option<i128> {
+    match answer {
+        Answer::Integer(value) => Some(*value as i128),
+        Answer::Signed(value) => Some(*value as i128),
+        Answer::Text(_) => None,
+    }
+}
+
+fn parse_wait(text: &str) -> Option<Duration> {
+    let (_, rest) = text.split_once("You have ")?;
+    let (wait, _) = rest.split_once(" left to wait")?;
+
+    wait.split_whitespace()
+        .map(|token| {
+            let (number, unit) = token.split_at(token.find(|c: char| !c.is_ascii_digit())?);
+            let number: u64 = number.parse().ok()?;
+            match unit {
+                "h" => Some(number * 3600),
+                "m" => Some(number * 60),
+                "s" => Some(number),
+                _ => None,
+            }
+        })
+        .sum::<Option<u64>>()
+        .map(Duration::from_secs)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use crate::{cli::Part, solution::Answer};
+
+    use super::{Submission, SubmissionLog, Verdict};
+
+    #[test]
+    fn responses_are_classified() {
+        assert_eq!(
+            Verdict::from_response(
+                "<article><p>That's the right answer!  You are <em>one gold star</em> closer.</p></article>"
+            ),
+            Verdict::Correct
+        );
+        assert_eq!(
+            Verdict::from_response(
+                "<p>That's not the right answer; your answer is too high.  If you're stuck...</p>"
+            ),
+            Verdict::TooHigh
+        );
+        assert_eq!(
+            Verdict::from_response("<p>That's not the right answer; your answer is too low.</p>"),
+            Verdict::TooLow
+        );
+        assert_eq!(
+            Verdict::from_response(
+                "<p>You gave an answer too recently; you have to wait after submitting an answer before trying again.  You have 1m 12s left to wait.</p>"
+            ),
+            Verdict::RateLimited(Some(Duration::from_secs(72)))
+        );
+        assert_eq!(
+            Verdict::from_response("<p>You don't seem to be solving the right level.  Did you already complete it?</p>"),
+            Verdict::AlreadySolved
+        );
+        assert!(
+            matches!(Verdict::from_response("<p>Huh?</p>"), Verdict::Unknown(text) if text == "Huh?")
+        );
+    }
+
+    #[test]
+    fn known_wrong_answers_are_refused() {
+        let log = SubmissionLog::parse(
+            "1701000000\t9\t1\t2000\ttoo-high
+1701000060\t9\t1\t1000\ttoo-low
+1701000120\t9\t1\t1500\twrong
+1701000180\t9\t2\t1031\tcorrect
+",
+        )
+        .unwrap();
+
+        assert!(log.check(9, Part::One, &Answer::Integer(2000)).is_err());
+        assert!(log.check(9, Part::One, &Answer::Integer(2500)).is_err());
+        assert!(log.check(9, Part::One, &Answer::Integer(999)).is_err());
+        assert!(log.check(9, Part::One, &Answer::Signed(1500)).is_err());
+        assert!(log.check(9, Part::One, &Answer::Integer(1884)).is_ok());
+        assert!(log.check(9, Part::Two, &Answer::Integer(1031)).is_err());
+        assert!(log.check(8, Part::One, &Answer::Integer(2000)).is_ok());
+        assert!(SubmissionLog::parse("1701000000\t9\t3\t2000\ttoo-high").is_err());
+    }
+
+    #[test]
+    fn submissions_are_appended_to_the_log() {
+        let path = std::env::temp_dir().join(format!("aoc2023-log-{}", std::process::id()));
+        let submission = Submission {
+            timestamp: 1701000000,
+            day: 7,
+            part: Part::Two,
+            answer: String::from("250577259"),
+            verdict: Verdict::RateLimited(Some(Duration::from_secs(30))),
+        };
+
+        SubmissionLog::append(&path, &submission).unwrap();
+        SubmissionLog::append(&path, &submission).unwrap();
+        let log = SubmissionLog::read(&path).unwrap();
+
+        assert_eq!(log.submissions.len(), 2);
+        assert_eq!(log.submissions[0].verdict, Verdict::RateLimited(None));
+        assert_eq!(log.submissions[1].answer, "250577259");
+        std::fs::remove_file(&path).unwrap();
+    }
+}
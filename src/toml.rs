@@ -0,0 +1,189 @@
+use std::{collections::BTreeMap, fmt::Display};
+
+use crate::{
+    error::Result,
+    utils::{numbered_lines, Line},
+};
+
+pub type Table = BTreeMap<String, Value>;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Value {
+    Integer(i128),
+    String(String),
+    Boolean(bool),
+}
+
+impl Display for Value {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Value::Integer(value) => value.fmt(f),
+            Value::String(value) => value.fmt(f),
+            Value::Boolean(value) => value.fmt(f),
+        }
+    }
+}
+
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct Document {
+    tables: BTreeMap<String, Table>,
+}
+
+impl Document {
+    pub fn parse(input: &str) -> Result<Self> {
+        let mut document = Document::default();
+        let mut current = String::new();
+
+        for line in numbered_lines(input) {
+            let text = strip_comment(line.text).trim();
+
+            if text.is_empty() {
+                continue;
+            }
+            if let Some(header) = text.strip_prefix('[') {
+                let Some(name) = header.strip_suffix(']') else {
+                    return Err(line.error_at(text, "expected `]` after table name"));
+                };
+                let name = name.trim();
+                if !is_bare_key(name) {
+                    return Err(line.error_at(name, format!("invalid table name `{name}`")));
+                }
+                if document.tables.contains_key(name) {
+                    return Err(line.error_at(name, format!("table `{name}` is defined twice")));
+                }
+                document.tables.insert(name.to_string(), Table::new());
+                current = name.to_string();
+                continue;
+            }
+
+            let Some((key, value)) = text.split_once('=') else {
+                return Err(line.error_at(text, "expected `key = value`"));
+            };
+            let (key, value) = (key.trim(), value.trim());
+            if !is_bare_key(key) {
+                return Err(line.error_at(key, format!("invalid key `{key}`")));
+            }
+            let value = parse_value(line, value)?;
+            let table = document.tables.entry(current.clone()).or_default();
+            if table.insert(key.to_string(), value).is_some() {
+                return Err(line.error_at(key, format!("key `{key}` is defined twice")));
+            }
+        }
+        Ok(document)
+    }
+
+    pub fn root(&self) -> Option<&Table> {
+        self.tables.get("")
+    }
+
+    pub fn tables(&self) -> impl Iterator<Item = (&str, &Table)> {
+        self.tables
+            .iter()
+            .filter(|(name, _)| !name.is_empty())
+            .map(|(name, table)| (name.as_str(), table))
+    }
+}
+
+fn strip_comment(text: &str) -> &str {
+    let mut in_string = false;
+    let mut escaped = false;
+
+    for (key, c) in text.char_indices() {
+        match c {
+            _ if escaped => escaped = false,
+            '\\' if in_string => escaped = true,
+            '"' => in_string = !in_string,
+            '#' if !in_string => return &text[..key],
+            _ => (),
+        }
+    }
+    text
+}
+
+fn is_bare_key(s: &str) -> bool {
+    !s.is_empty()
+        && s.bytes()
+            .all(|byte| byte.is_ascii_alphanumeric() || byte == b'_' || byte == b'-')
+}
+
+fn parse_value(line: Line, s: &str) -> Result<Value> {
+    match s {
+        "" => Err(line.error_at_column(line.text.len(), 1, "missing value")),
+        "true" => Ok(Value::Boolean(true)),
+        "false" => Ok(Value::Boolean(false)),
+        _ if s.starts_with('"') => parse_string(line, s).map(Value::String),
+        _ => s
+            .replace('_', "")
+            .parse()
+            .map(Value::Integer)
+            .map_err(|_| line.error_at(s, format!("invalid value `{s}`"))),
+    }
+}
+
+fn parse_string(line: Line, s: &str) -> Result<String> {
+    let mut parsed = String::new();
+    let mut chars = s.char_indices().skip(1);
+
+    while let Some((key, c)) = chars.next() {
+        match c {
+            '"' if key + 1 == s.len() => return Ok(parsed),
+            '"' => return Err(line.error_at(&s[key + 1..], "unexpected characters after string")),
+            '\\' => match chars.next() {
+                Some((_, '"')) => parsed.push('"'),
+                Some((_, '\\')) => parsed.push('\\'),
+                Some((_, 'n')) => parsed.push('\n'),
+                Some((_, 't')) => parsed.push('\t'),
+                _ => return Err(line.error_at(&s[key..], "invalid escape sequence")),
+            },
+            _ => parsed.push(c),
+        }
+    }
+    Err(line.error_at(s, "unterminated string"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Document, Value};
+
+    #[test]
+    fn documents_are_parsed() {
+        let document = Document::parse(
+            "title = \"Advent # of Code\" # comment
+
+[day01]
+part1 = 55_607
+part2 = -3
+done = true
+",
+        )
+        .unwrap();
+        let tables: Vec<_> = document.tables().collect();
+
+        assert_eq!(
+            document.root().unwrap()["title"],
+            Value::String(String::from("Advent # of Code"))
+        );
+        assert_eq!(tables.len(), 1);
+        assert_eq!(tables[0].0, "day01");
+        assert_eq!(tables[0].1["part1"], Value::Integer(55607));
+        assert_eq!(tables[0].1["part2"].to_string(), "-3");
+        assert_eq!(tables[0].1["done"], Value::Boolean(true));
+    }
+
+    #[test]
+    fn malformed_documents_are_errors() {
+        let err = Document::parse("[day01]\npart1 = 12x").unwrap_err();
+        assert_eq!(err.to_string(), "line 2, column 9: invalid value `12x`");
+
+        let err = Document::parse("[day01]\npart1 = 1\npart1 = 2").unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "line 3, column 1: key `part1` is defined twice"
+        );
+
+        assert!(Document::parse("[day01").is_err());
+        assert!(Document::parse("part1 = \"abc").is_err());
+        assert!(Document::parse("part1 = \"a\"b").is_err());
+        assert!(Document::parse("part 1 = 2").is_err());
+    }
+}
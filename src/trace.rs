@@ -0,0 +1,144 @@
+use std::{
+    cell::Cell,
+    fmt::{Arguments, Display},
+    sync::atomic::{AtomicU32, AtomicU8, Ordering},
+};
+
+use crate::cli::DaySelection;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Level {
+    Info = 1,
+    Debug = 2,
+    Trace = 3,
+}
+
+impl Level {
+    pub fn from_verbosity(count: usize) -> Option<Self> {
+        match count {
+            0 => None,
+            1 => Some(Level::Info),
+            2 => Some(Level::Debug),
+            _ => Some(Level::Trace),
+        }
+    }
+}
+
+impl Display for Level {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Level::Info => write!(f, "info"),
+            Level::Debug => write!(f, "debug"),
+            Level::Trace => write!(f, "trace"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Filter {
+    pub level: Level,
+    pub days: DaySelection,
+}
+
+static LEVEL: AtomicU8 = AtomicU8::new(0);
+static DAYS: AtomicU32 = AtomicU32::new(0);
+
+thread_local! {
+    static DAY: Cell<u8> = const { Cell::new(0) };
+}
+
+macro_rules! trace {
+    ($level:ident, $($arg:tt)+) => {
+        if cfg!(debug_assertions) && $crate::trace::enabled($crate::trace::Level::$level) {
+            $crate::trace::write($crate::trace::Level::$level, format_args!($($arg)+));
+        }
+    };
+}
+
+pub(crate) use trace;
+
+pub fn available() -> bool {
+    cfg!(debug_assertions)
+}
+
+pub fn configure(filter: Option<&Filter>) {
+    let (level, days) = masks(filter);
+    LEVEL.store(level, Ordering::Relaxed);
+    DAYS.store(days, Ordering::Relaxed);
+}
+
+pub fn in_day<T>(day: u8, f: impl FnOnce() -> T) -> T {
+    let previous = DAY.with(|current| current.replace(day));
+    let result = f();
+    DAY.with(|current| current.set(previous));
+    result
+}
+
+pub fn enabled(level: Level) -> bool {
+    available()
+        && allows(
+            (LEVEL.load(Ordering::Relaxed), DAYS.load(Ordering::Relaxed)),
+            level,
+            DAY.with(Cell::get),
+        )
+}
+
+pub fn write(level: Level, args: Arguments) {
+    let day = DAY.with(Cell::get);
+    eprintln!("[day{day:02} {level}] {args}");
+}
+
+fn masks(filter: Option<&Filter>) -> (u8, u32) {
+    match filter {
+        Some(Filter { level, days }) => (
+            *level as u8,
+            match days {
+                DaySelection::All => u32::MAX,
+                DaySelection::Days(days) => days.iter().fold(0, |mask, day| mask | 1 << day),
+            },
+        ),
+        None => (0, 0),
+    }
+}
+
+fn allows((max_level, days): (u8, u32), level: Level, day: u8) -> bool {
+    level as u8 <= max_level && days & 1 << day != 0
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::cli::DaySelection;
+
+    use super::{allows, masks, Filter, Level};
+
+    #[test]
+    fn traces_are_filtered_by_level_and_day() {
+        let filter = Filter {
+            level: Level::Debug,
+            days: DaySelection::Days(vec![5, 7]),
+        };
+        let levels = |filter, day| {
+            [Level::Info, Level::Debug, Level::Trace].map(|level| allows(masks(filter), level, day))
+        };
+
+        assert_eq!(levels(Some(&filter), 7), [true, true, false]);
+        assert_eq!(levels(Some(&filter), 5), [true, true, false]);
+        assert_eq!(levels(Some(&filter), 8), [false; 3]);
+        assert_eq!(levels(Some(&filter), 0), [false; 3]);
+        assert_eq!(levels(None, 7), [false; 3]);
+
+        let all = Filter {
+            level: Level::Trace,
+            days: DaySelection::All,
+        };
+        assert_eq!(levels(Some(&all), 25), [true; 3]);
+    }
+
+    #[test]
+    fn verbosity_selects_the_level() {
+        assert_eq!(Level::from_verbosity(0), None);
+        assert_eq!(Level::from_verbosity(1), Some(Level::Info));
+        assert_eq!(Level::from_verbosity(3), Some(Level::Trace));
+        assert_eq!(Level::from_verbosity(5), Some(Level::Trace));
+    }
+}
@@ -1,9 +1,130 @@
+use crate::error::AocError;
+
 pub const LINE_FEED: u8 = 10;
 pub const CARRIAGE_RETURN: u8 = 13;
 
-pub fn parse_from_bytes(input: &[u8]) -> impl Iterator<Item = impl Iterator<Item = u8> + '_> {
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Line<'a> {
+    pub number: usize,
+    pub text: &'a str,
+}
+
+impl<'a> Line<'a> {
+    pub fn column_of(&self, token: &str) -> usize {
+        let start = self.text.as_ptr() as usize;
+        let token_start = token.as_ptr() as usize;
+
+        if (start..=start + self.text.len()).contains(&token_start) {
+            token_start - start
+        } else {
+            0
+        }
+    }
+
+    pub fn error(&self, message: impl Into<String>) -> AocError {
+        AocError::parse(message)
+            .with_span(0, self.text.len(), self.text)
+            .with_line(self.number)
+    }
+
+    pub fn error_at(&self, token: &str, message: impl Into<String>) -> AocError {
+        self.error_at_column(self.column_of(token), token.len(), message)
+    }
+
+    pub fn error_at_column(
+        &self,
+        column: usize,
+        width: usize,
+        message: impl Into<String>,
+    ) -> AocError {
+        AocError::parse(message)
+            .with_span(column, width, self.text)
+            .with_line(self.number)
+    }
+}
+
+pub fn numbered_lines(input: &str) -> impl Iterator<Item = Line<'_>> {
+    input.lines().enumerate().map(|(key, text)| Line {
+        number: key + 1,
+        text,
+    })
+}
+
+pub fn numbered_lines_from_bytes(input: &[u8]) -> impl Iterator<Item = (usize, &[u8])> {
     input
         .split(|int| *int == LINE_FEED)
-        .filter(|line| !line.is_empty())
-        .map(|line| line.iter().filter(|c| *c != &CARRIAGE_RETURN).copied())
+        .map(|line| line.strip_suffix(&[CARRIAGE_RETURN]).unwrap_or(line))
+        .enumerate()
+        .map(|(key, line)| (key + 1, line))
+}
+
+pub fn byte_error(
+    line: (usize, &[u8]),
+    column: usize,
+    width: usize,
+    message: impl Into<String>,
+) -> AocError {
+    let (number, bytes) = line;
+
+    AocError::parse(message)
+        .with_span(column, width, &String::from_utf8_lossy(bytes))
+        .with_line(number)
+}
+
+pub fn strip_tags(html: &str) -> String {
+    let mut text = String::with_capacity(html.len());
+    let mut in_tag = false;
+
+    for c in html.chars() {
+        match c {
+            '<' => in_tag = true,
+            '>' if in_tag => in_tag = false,
+            _ if !in_tag => text.push(c),
+            _ => (),
+        }
+    }
+    text
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{byte_error, numbered_lines, numbered_lines_from_bytes, strip_tags};
+
+    #[test]
+    fn lines_track_numbers_and_columns() {
+        let lines: Vec<_> = numbered_lines("seeds: 79 14\n\nseed-to-soil map:").collect();
+        let token = lines[0].text.split_whitespace().nth(2).unwrap();
+
+        assert_eq!(lines.len(), 3);
+        assert_eq!(lines[2].number, 3);
+        assert_eq!(lines[0].column_of(token), 10);
+        assert_eq!(lines[0].column_of("14"), 0);
+        assert_eq!(
+            lines[0].error_at(token, "bad seed").to_string(),
+            "line 1, column 11: bad seed"
+        );
+    }
+
+    #[test]
+    fn tags_are_stripped() {
+        assert_eq!(
+            strip_tags("<p>That's the <em>right</em> answer!</p>"),
+            "That's the right answer!"
+        );
+        assert_eq!(strip_tags("a < b"), "a ");
+    }
+
+    #[test]
+    fn byte_lines_track_numbers() {
+        let lines: Vec<_> = numbered_lines_from_bytes(b"LR\r\n\r\nAAA").collect();
+
+        assert_eq!(
+            lines,
+            vec![(1, &b"LR"[..]), (2, &b""[..]), (3, &b"AAA"[..])]
+        );
+        assert_eq!(
+            byte_error(lines[2], 1, 1, "bad node").to_string(),
+            "line 3, column 2: bad node"
+        );
+    }
 }
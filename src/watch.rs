@@ -0,0 +1,163 @@
+use std::{
+    collections::BTreeMap,
+    fs,
+    path::{Path, PathBuf},
+    time::{Duration, SystemTime},
+};
+
+use crate::{
+    bench::format_duration,
+    fixtures::ANSWERS_FILE,
+    input::{day_input_path, InputSource},
+    report::Status,
+};
+
+pub const DEFAULT_INTERVAL: Duration = Duration::from_millis(500);
+
+pub type Snapshot = BTreeMap<PathBuf, Option<(SystemTime, u64)>>;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Outcome {
+    pub status: Status,
+    pub expected: Option<String>,
+    pub duration: Duration,
+}
+
+pub fn watched_paths(input: &InputSource, fixtures: &Path, day: u8) -> Vec<PathBuf> {
+    let day_dir = fixtures.join(format!("day{day:02}"));
+    let mut paths = vec![day_dir.join(ANSWERS_FILE)];
+
+    match input {
+        InputSource::Dir(dir) => paths.push(day_input_path(dir, day)),
+        InputSource::File(path) => paths.push(path.clone()),
+        InputSource::Stdin => (),
+    }
+    if let Ok(entries) = fs::read_dir(&day_dir) {
+        paths.extend(entries.filter_map(|entry| Some(entry.ok()?.path())));
+    }
+    paths.sort();
+    paths.dedup();
+    paths
+}
+
+pub fn snapshot(paths: &[PathBuf]) -> Snapshot {
+    paths
+        .iter()
+        .map(|path| {
+            let metadata = fs::metadata(path).ok();
+            let stamp =
+                metadata.and_then(|metadata| Some((metadata.modified().ok()?, metadata.len())));
+            (path.clone(), stamp)
+        })
+        .collect()
+}
+
+pub fn changed_paths(before: &Snapshot, after: &Snapshot) -> Vec<PathBuf> {
+    let mut changed: Vec<PathBuf> = after
+        .iter()
+        .filter(|(path, stamp)| before.get(*path) != Some(stamp))
+        .map(|(path, _)| path.clone())
+        .collect();
+
+    changed.extend(
+        before
+            .keys()
+            .filter(|path| !after.contains_key(*path))
+            .cloned(),
+    );
+    changed
+}
+
+pub fn describe(outcome: &Outcome, previous: Option<&Outcome>) -> String {
+    let mut text = match (&outcome.status, &outcome.expected) {
+        (Status::Ok(answer), Some(expected)) if answer.to_string() == *expected => {
+            format!("{answer} ok")
+        }
+        (Status::Ok(answer), Some(expected)) => format!("{answer}, expected {expected}"),
+        (Status::Ok(answer), None) => answer.to_string(),
+        (Status::Unimplemented, _) => String::from("not implemented yet"),
+        (Status::Error(err), _) => format!("failed: {err}"),
+    };
+    text.push_str(&format!(" in {}", format_duration(outcome.duration)));
+
+    if let Some(previous) = previous {
+        let change = match &previous.status {
+            status if *status == outcome.status => String::from("unchanged"),
+            Status::Ok(answer) => format!("was {answer}"),
+            Status::Unimplemented => String::from("was not implemented"),
+            Status::Error(_) => String::from("was failing"),
+        };
+        text.push_str(&format!(
+            " ({change}, previously {})",
+            format_duration(previous.duration)
+        ));
+    }
+    text
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{fs, path::PathBuf, time::Duration};
+
+    use crate::{input::InputSource, report::Status, solution::Answer};
+
+    use super::{changed_paths, describe, snapshot, watched_paths, Outcome};
+
+    #[test]
+    fn changes_are_detected_between_snapshots() {
+        let dir = std::env::temp_dir().join(format!("aoc2023-watch-{}", std::process::id()));
+        let input = dir.join("input.txt");
+        fs::create_dir_all(dir.join("fixtures").join("day09")).unwrap();
+        fs::write(&input, "0 3 6").unwrap();
+        fs::write(
+            dir.join("fixtures").join("day09").join("example1.txt"),
+            "1 2",
+        )
+        .unwrap();
+
+        let paths = watched_paths(&InputSource::File(input.clone()), &dir.join("fixtures"), 9);
+        assert_eq!(paths.len(), 3);
+        let before = snapshot(&paths);
+        assert_eq!(changed_paths(&before, &before), Vec::<PathBuf>::new());
+        assert_eq!(changed_paths(&Default::default(), &before).len(), 3);
+
+        fs::write(&input, "0 3 6 9").unwrap();
+        assert_eq!(changed_paths(&before, &snapshot(&paths)), vec![input]);
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn outcomes_are_compared_with_the_previous_run() {
+        let outcome = |answer: u64, expected: Option<&str>, micros| Outcome {
+            status: Status::Ok(Answer::Integer(answer)),
+            expected: expected.map(String::from),
+            duration: Duration::from_micros(micros),
+        };
+
+        assert_eq!(describe(&outcome(114, None, 20), None), "114 in 20.00µs");
+        assert_eq!(
+            describe(
+                &outcome(114, Some("114"), 20),
+                Some(&outcome(114, None, 30))
+            ),
+            "114 ok in 20.00µs (unchanged, previously 30.00µs)"
+        );
+        assert_eq!(
+            describe(
+                &outcome(115, Some("114"), 20),
+                Some(&outcome(114, None, 20))
+            ),
+            "115, expected 114 in 20.00µs (was 114, previously 20.00µs)"
+        );
+        let failed = Outcome {
+            status: Status::Error(String::from("line 1: invalid number")),
+            expected: None,
+            duration: Duration::from_micros(5),
+        };
+        assert_eq!(
+            describe(&outcome(114, None, 20), Some(&failed)),
+            "114 in 20.00µs (was failing, previously 5.00µs)"
+        );
+    }
+}